@@ -10,18 +10,18 @@ pub struct Foo {
 }
 
 fn main() {
-    let mut foo = Foo {
+    let mut value = Foo {
         a: vec![1, 2, 3],
         b: 3,
         e: 100,
     };
-    foo.clear();
-    assert_eq!(foo.e, 0);
-    assert_eq!(foo.a, vec![]);
-    foo.a.push(1);
-    assert_eq!(foo.a, vec![1]);
-    foo.a.clear();
-    assert_eq!(foo.a, vec![]);
-    assert_eq!(foo.b, 3);
+    value.clear();
+    assert_eq!(value.e, 0);
+    assert_eq!(value.a, vec![]);
+    value.a.push(1);
+    assert_eq!(value.a, vec![1]);
+    value.a.clear();
+    assert_eq!(value.a, vec![]);
+    assert_eq!(value.b, 3);
 }
 
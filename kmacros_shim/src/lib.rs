@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 pub struct HiddenVariable;
 /// ```
 /// use std::any::TypeId;
@@ -76,5 +77,22 @@ macro_rules! hide {
     };
 }
 
+/// Clears a buffer when the enclosing scope ends, by shadowing it with the guard from
+/// `kmacros::Clearable::scoped`. Either takes a variable, or a `let` binding to a `&mut`.
+///
+/// The expansion refers to `kmacros`, so using the macro needs `kmacros` as a dependency. See
+/// `kproc_macros/tests/21-clearable-scoped.rs` for a tested example.
+#[macro_export]
+macro_rules! cleared_scope {
+    (let $name:ident = $value:expr $(;)?) => {
+        #[allow(unused_mut)]
+        let mut $name = ::kmacros::Clearable::scoped($value);
+    };
+    ($name:ident $(;)?) => {
+        #[allow(unused_mut)]
+        let mut $name = ::kmacros::Clearable::scoped(&mut $name);
+    };
+}
+
 #[cfg(test)]
 mod tests {}
@@ -1,203 +1,1449 @@
-//! For structs with named fields generates an impl that would return a doc comment for a field, if
-//! one is available:
+//! Generates an impl of `kmacros::Clearable` which clears every field in place. Primitive fields,
+//! such as integers, floats, `bool` and `char`, as well as `()` and `PhantomData`, are reset to
+//! their default.
 //!
+//! Fields can be annotated with `#[clearable(skip)]`, `#[clearable(default)]`,
+//! `#[clearable(with = path::to::function)]`, which is called with `&mut field`, or
+//! `#[clearable(value = expr)]`, which assigns `expr` to the field. Unknown, repeated or
+//! conflicting attributes, such as `skip` together with `default`, are compile errors.
 //!
 //! ```rust
-//! # use kmacros::FieldIter;
-//! #[derive(Debug, FieldIter)]
-//! /// outer
-//! #[allow(dead_code)]
-//! #[field_iter(debug_iter = "dyn Debug")]
-//! struct Foo<T> {
-//!    x: bool,
-//!    b: String,
-//!    t: T,
+//! # use kmacros::Clearable;
+//! mod counters {
+//!     pub fn restart(counter: &mut u32) {
+//!         *counter = 1;
+//!     }
 //! }
 //!
+//! #[derive(Clearable)]
+//! struct Foo {
+//!     a: Vec<i32>,
+//!     #[clearable(skip)]
+//!     b: u32,
+//!     #[clearable(default)]
+//!     c: u32,
+//!     #[clearable(with = counters::restart)]
+//!     d: u32,
+//!     #[clearable(value = format!("{}-{}", "id", 0))]
+//!     e: String,
+//! }
+//! ```
+//!
+//! `#[clearable(zeroize)]` clears a field with `kmacros::SecureClearable`, which overwrites the
+//! old contents with zeros first. Like `with` and `value`, such fields aren't checked by
+//! `IsCleared`.
+//!
+//! The older string forms are still accepted: `#[clearable(expr = "{}.push(1)")]`, where `{}` is
+//! replaced by the field, and `#[clearable(raw_expr = "...")]`, used as is.
+//!
+//! Tuple structs are cleared through their positional fields, and unit structs get a no-op impl.
+//!
+//! ```rust
+//! # use kmacros::Clearable;
+//! #[derive(Clearable)]
+//! struct Scratch(Vec<u8>, #[clearable(default)] u32);
+//!
+//! #[derive(Clearable)]
+//! struct Marker;
+//! ```
+//!
+//! `#[clearable(shrink_to = N)]` on a field, or on the container for every field, clears with
+//! `Clearable::clear_and_shrink_to` so buffers don't keep more than `N` elements of capacity. The
+//! generated `clear_and_shrink_to` forwards to every cleared field, using the smaller limit when a
+//! field has its own.
+//!
+//! ```rust
+//! # use kmacros::Clearable;
+//! #[derive(Clearable)]
+//! #[clearable(shrink_to = 4096)]
+//! struct Request {
+//!     body: Vec<u8>,
+//!     #[clearable(shrink_to = 16)]
+//!     headers: Vec<(String, String)>,
+//! }
+//! ```
+//!
+//! The generated `Clearable::clear_with_report` records every cleared field in a
+//! `kmacros::ClearReport` under its path, e.g. `headers` or `inner.items`, honoring `shrink_to`.
+//! An enum switched back to its reset variant records one entry under its own path, with the
+//! name of the replaced variant in `replaced` and `dropped` at 0, since its fields aren't counted.
+//!
+//! Bounds are inferred for field types which mention a type parameter, e.g. `Vec<T>: Clearable`.
+//! They can be replaced with `#[clearable(bound = "T: Clearable")]` on the container. The bounds
+//! of the `IsCleared` impl are inferred separately and replaced with
+//! `#[clearable(bound(is_cleared = "T: IsCleared"))]`, `bound(clearable = "...")` being the same
+//! as `bound = "..."`.
+//!
+//! ```rust
+//! # use kmacros::Clearable;
+//! #[derive(Clearable)]
+//! struct Buffers<'a, T: Clone, const N: usize = 4> {
+//!     items: Vec<T>,
+//!     #[clearable(skip)]
+//!     name: &'a str,
+//!     chunks: [Vec<u8>; N],
+//! }
 //!
-//! Foo {
-//!     x: true,
-//!     b: format!("Test"),
-//!     t: 64u64,
-//! }.debug_iter(|name, value| {
-//!   println!("{name} = {value:?}");
-//!   None
-//! });
+//! #[derive(Clearable)]
+//! #[clearable(bound = "T: Clearable + Copy")]
+//! struct Wrapper<T>(T);
 //! ```
-use proc_macro2::Ident;
-use quote::ToTokens;
-use syn::{
-    parse::{Parse, ParseStream},
-    token, AngleBracketedGenericArguments, Attribute, LitStr, Visibility,
-};
+//!
+//! `#[clearable(before = Self::flush, after = Self::reinit)]` on the container calls the given
+//! functions with `&mut self` around the field clears in `clear` and `clear_and_shrink_to`, to
+//! flush data first or restore invariants afterwards. Clearing a group doesn't call them.
+//!
+//! ```rust
+//! # use kmacros::Clearable;
+//! #[derive(Clearable)]
+//! #[clearable(before = "Self::flush", after = "Self::reinit")]
+//! struct Log {
+//!     lines: Vec<String>,
+//!     #[clearable(skip)]
+//!     flushed: usize,
+//!     #[clearable(skip)]
+//!     epoch: u64,
+//! }
+//!
+//! impl Log {
+//!     fn flush(&mut self) {
+//!         self.flushed += self.lines.len();
+//!     }
+//!
+//!     fn reinit(&mut self) {
+//!         self.epoch += 1;
+//!     }
+//! }
+//!
+//! let mut log = Log { lines: vec!["a".into()], flushed: 0, epoch: 0 };
+//! log.clear();
+//! assert_eq!((log.lines.len(), log.flushed, log.epoch), (0, 1, 1));
+//! ```
+//!
+//! `#[derive(IsCleared)]` implements `kmacros::IsCleared` from the same attributes. Add
+//! `#[clearable(assert_cleared)]` to have `Clearable::debug_assert_cleared` check it, which
+//! `Clearable::cleared` and the pools in `kmacros::pool` call in debug builds.
+//!
+//! ```rust
+//! # use kmacros::{Clearable, IsCleared};
+//! #[derive(Clearable, IsCleared)]
+//! #[clearable(assert_cleared)]
+//! struct Foo {
+//!     a: Vec<i32>,
+//!     #[clearable(default)]
+//!     c: u32,
+//! }
+//!
+//! let mut foo = Foo { a: vec![1], c: 2 };
+//! assert!(!foo.is_cleared());
+//! assert!(foo.cleared().is_cleared());
+//! ```
+//!
+//! `#[clearable(check_default)]` on the container makes `clear` check, in debug builds, that every
+//! field which isn't skipped ends up equal to the one from `Default::default()`. This catches a
+//! `clear` drifting from `Default` as fields are added. It requires `Self: Default` and the fields
+//! to be `PartialEq`.
+//!
+//! ```rust
+//! # use kmacros::Clearable;
+//! #[derive(Default, Clearable)]
+//! #[clearable(check_default)]
+//! struct Counter {
+//!     hits: Vec<u32>,
+//!     #[clearable(value = 0)]
+//!     total: u32,
+//!     #[clearable(skip)]
+//!     resets: u32,
+//! }
+//! ```
+//!
+//! Enums must mark one variant with `#[clearable(reset)]`. Clearing an enum which is already in
+//! that variant clears its fields in place, so buffers keep their capacity. Any other variant is
+//! replaced by the reset variant with every field set to `Default::default()`. Since only the
+//! reset variant is ever cleared, field attributes are rejected on the other variants.
+//!
+//! ```rust
+//! # use kmacros::Clearable;
+//! #[derive(Clearable)]
+//! enum State {
+//!     #[clearable(reset)]
+//!     Idle { buffer: Vec<u8> },
+//!     Running(u32),
+//! }
+//! ```
+//!
+//! Fields can be put into named groups with `#[clearable(group = "frame")]`, repeated for several
+//! groups. Each group gets a `clear_frame()` method which only clears the fields in it, and calls
+//! `Clearable::clear_group` on the other fields so nested structs clear their own `frame` fields.
+//! `clear()` still clears everything. Enums only clear groups while in the reset variant. Groups
+//! can't be named `field`, `group`, `with_report` or `and_shrink_to`, whose methods would shadow
+//! those of `Clearable`.
+//!
+//! ```rust
+//! # use kmacros::Clearable;
+//! #[derive(Clearable)]
+//! struct Level {
+//!     #[clearable(group = "frame")]
+//!     particles: Vec<u32>,
+//!     enemies: Vec<u32>,
+//! }
+//!
+//! #[derive(Clearable)]
+//! struct World {
+//!     #[clearable(group = "frame")]
+//!     events: Vec<String>,
+//!     level: Level,
+//! }
+//!
+//! let mut world = World {
+//!     events: vec!["spawn".into()],
+//!     level: Level { particles: vec![1], enemies: vec![2] },
+//! };
+//! world.clear_frame();
+//! assert!(world.events.is_empty() && world.level.particles.is_empty());
+//! assert_eq!(world.level.enemies, [2]);
+//! ```
+//!
+//! `#[clearable(by_name)]` on a struct implements `Clearable::clear_field`, which clears a field
+//! picked at runtime, and adds a `CLEARABLE_FIELDS` constant listing the names it accepts. Skipped
+//! fields aren't listed, and the others are reset as `clear` would. A dotted path such as
+//! `"physics.particles"` is forwarded to the field's own `clear_field`. Like groups, this doesn't
+//! call the `before` and `after` hooks.
+//!
+//! ```rust
+//! # use kmacros::Clearable;
+//! #[derive(Clearable)]
+//! #[clearable(by_name)]
+//! struct Physics {
+//!     particles: Vec<u32>,
+//!     #[clearable(skip)]
+//!     gravity: f32,
+//! }
+//!
+//! #[derive(Clearable)]
+//! #[clearable(by_name)]
+//! struct State {
+//!     physics: Physics,
+//!     log: Vec<String>,
+//! }
+//!
+//! let mut state = State {
+//!     physics: Physics { particles: vec![1], gravity: 9.8 },
+//!     log: vec!["start".into()],
+//! };
+//! assert_eq!(State::CLEARABLE_FIELDS, ["physics", "log"]);
+//! assert!(state.clear_field("physics.particles"));
+//! assert!(!state.clear_field("physics.gravity"));
+//! assert!(state.physics.particles.is_empty() && state.log.len() == 1);
+//! ```
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use std::collections::HashSet;
+use syn::{ext::IdentExt, parse::Parse, punctuated::Punctuated, token, Attribute, Visibility};
+
+/// How a single field is reset. Skipped fields don't have one.
+enum FieldClear {
+    /// `Clearable::clear(&mut field)`
+    Clear,
+    /// `field = Default::default()`
+    Default,
+    /// Expression from `expr` with `{}` replaced by the field.
+    Expr(syn::Expr),
+    /// Expression from `raw_expr`, used as is.
+    RawExpr(syn::Expr),
+    /// `with = path`, called as `path(&mut field)`.
+    With(syn::Path),
+    /// `value = expr`, assigned to the field.
+    Value(syn::Expr),
+    /// `kmacros::SecureClearable::secure_clear(&mut field)`
+    Zeroize,
+}
+
+struct Field {
+    member: syn::Member,
+    ty: syn::Type,
+    clear: Option<FieldClear>,
+    /// From `shrink_to = N`, only used for `FieldClear::Clear`.
+    shrink_to: Option<syn::LitInt>,
+    /// From `group = "name"`, which can be repeated.
+    groups: Vec<syn::LitStr>,
+}
+
+/// The value a `#[clearable(...)]` key takes.
+#[derive(Clone, Copy)]
+enum Kind {
+    /// Just `key`.
+    Flag,
+    /// `key = "..."`
+    Str,
+    /// `key = N`
+    Int,
+    /// `key = some::path` or `key = "some::path"`
+    Path,
+    /// `key = <expression>`
+    Expr,
+    /// `key = "..."` or `key(impl_name = "...", ...)`
+    Bound,
+}
 
-struct Doc(pub String);
-impl Parse for Doc {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        input.parse::<token::Eq>()?;
-        let s = input.parse::<LitStr>()?.value();
-        Ok(Doc(s.trim_start().to_string()))
+enum AttrValue {
+    Flag,
+    Str(syn::LitStr),
+    Int(syn::LitInt),
+    Path(syn::Path),
+    Expr(Box<syn::Expr>),
+    /// The impl each string is for, `None` for the plain `key = "..."` form.
+    Bounds(Vec<(Option<Ident>, syn::LitStr)>),
+}
+
+/// One `key` or `key = value` entry of a `#[clearable(...)]` attribute.
+struct AttrItem {
+    key: Ident,
+    value: AttrValue,
+}
+
+impl AttrItem {
+    fn name(&self) -> String {
+        self.key.to_string()
     }
 }
 
+const FIELD_KEYS: &[(&str, Kind)] = &[
+    ("skip", Kind::Flag),
+    ("default", Kind::Flag),
+    ("expr", Kind::Str),
+    ("raw_expr", Kind::Str),
+    ("with", Kind::Path),
+    ("value", Kind::Expr),
+    ("zeroize", Kind::Flag),
+    ("shrink_to", Kind::Int),
+    ("group", Kind::Str),
+];
+
+const CONTAINER_KEYS: &[(&str, Kind)] = &[
+    ("bound", Kind::Bound),
+    ("shrink_to", Kind::Int),
+    ("assert_cleared", Kind::Flag),
+    ("check_default", Kind::Flag),
+    ("before", Kind::Path),
+    ("after", Kind::Path),
+    ("by_name", Kind::Flag),
+];
+
+const VARIANT_KEYS: &[(&str, Kind)] = &[("reset", Kind::Flag)];
+
+/// Group names whose `clear_{name}` method would shadow a method of `Clearable`.
+const RESERVED_GROUPS: &[&str] = &["and_shrink_to", "field", "group", "with_report"];
+
+/// Parses the entries of every `#[clearable(...)]` in `attrs`, allowing only `keys`.
+fn parse_attrs(attrs: &[Attribute], keys: &[(&str, Kind)]) -> syn::Result<Vec<AttrItem>> {
+    let mut items = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("clearable")) {
+        let parsed = attr.parse_args_with(|input: syn::parse::ParseStream| {
+            let mut items = vec![];
+            while !input.is_empty() {
+                items.push(parse_attr_item(input, keys)?);
+                if input.is_empty() {
+                    break;
+                }
+                input.parse::<token::Comma>()?;
+            }
+            Ok(items)
+        })?;
+        items.extend(parsed);
+    }
+    Ok(items)
+}
+
+fn parse_attr_item(input: syn::parse::ParseStream, keys: &[(&str, Kind)]) -> syn::Result<AttrItem> {
+    let key = input.parse::<Ident>()?;
+    let name = key.to_string();
+    let kind = match keys.iter().find(|(k, _)| *k == name) {
+        Some(&(_, kind)) => kind,
+        None => return Err(unknown_key(&key, keys)),
+    };
+    let value = match kind {
+        Kind::Flag => {
+            if input.peek(token::Eq) {
+                return Err(input.error(format!("`{}` doesn't take a value", name)));
+            }
+            AttrValue::Flag
+        }
+        Kind::Bound if input.peek(token::Paren) => {
+            let content;
+            syn::parenthesized!(content in input);
+            let mut bounds = vec![];
+            while !content.is_empty() {
+                let target = content.parse::<Ident>()?;
+                if target != "clearable" && target != "is_cleared" {
+                    return Err(syn::Error::new_spanned(
+                        &target,
+                        format!(
+                            "unknown bound target `{}`, expected `clearable` or `is_cleared`",
+                            target
+                        ),
+                    ));
+                }
+                content.parse::<token::Eq>()?;
+                bounds.push((Some(target), content.parse()?));
+                if content.is_empty() {
+                    break;
+                }
+                content.parse::<token::Comma>()?;
+            }
+            AttrValue::Bounds(bounds)
+        }
+        _ => {
+            if !input.peek(token::Eq) {
+                return Err(syn::Error::new_spanned(
+                    &key,
+                    format!("expected a value: `{} = ...`", name),
+                ));
+            }
+            input.parse::<token::Eq>()?;
+            match kind {
+                Kind::Str => AttrValue::Str(input.parse()?),
+                Kind::Bound => AttrValue::Bounds(vec![(None, input.parse()?)]),
+                Kind::Int => AttrValue::Int(input.parse()?),
+                Kind::Path if input.peek(syn::LitStr) => {
+                    AttrValue::Path(input.parse::<syn::LitStr>()?.parse()?)
+                }
+                Kind::Path => AttrValue::Path(input.parse()?),
+                _ => AttrValue::Expr(Box::new(input.parse()?)),
+            }
+        }
+    };
+    Ok(AttrItem { key, value })
+}
+
+fn unknown_key(key: &Ident, keys: &[(&str, Kind)]) -> syn::Error {
+    let name = key.to_string();
+    let suggestion = keys
+        .iter()
+        .map(|(k, _)| (edit_distance(&name, k), *k))
+        .filter(|&(distance, k)| distance <= 2 || k.starts_with(&name))
+        .min();
+    let message = match suggestion {
+        Some((_, k)) => format!(
+            "unknown clearable attribute `{}`, did you mean `{}`?",
+            name, k
+        ),
+        None => {
+            let expected = keys.iter().map(|(k, _)| *k).collect::<Vec<_>>();
+            format!(
+                "unknown clearable attribute `{}`, expected one of: {}",
+                name,
+                expected.join(", ")
+            )
+        }
+    };
+    syn::Error::new_spanned(key, message)
+}
+
+/// Levenshtein distance, for suggesting attribute names.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(previous + 1).min(row[j] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Errors for a key given twice, except for the `repeatable` ones.
+fn check_duplicates(items: &[AttrItem], repeatable: &[&str]) -> syn::Result<()> {
+    for (i, item) in items.iter().enumerate() {
+        let name = item.name();
+        if !repeatable.contains(&name.as_str()) && items[..i].iter().any(|x| x.name() == name) {
+            return Err(syn::Error::new_spanned(
+                &item.key,
+                format!("duplicate clearable attribute `{}`", name),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Parses `source`, reporting errors at the string literal it came from.
+fn parse_expr(lit: &syn::LitStr, source: &str) -> syn::Result<syn::Expr> {
+    syn::parse_str(source)
+        .map_err(|e| syn::Error::new(lit.span(), format!("invalid expression: {}", e)))
+}
+
+fn conflict(first: &Ident, second: &Ident) -> syn::Error {
+    syn::Error::new_spanned(
+        second,
+        format!(
+            "conflicting clearable attributes `{}` and `{}`",
+            first, second
+        ),
+    )
+}
+
+/// Parses the `#[clearable(...)]` attributes of a field. `place` is substituted for `{}` in
+/// `expr` attributes.
+fn parse_field(f: &syn::Field, member: syn::Member, place: &str) -> syn::Result<Field> {
+    let mut field = Field {
+        member,
+        ty: f.ty.clone(),
+        clear: Some(FieldClear::Clear),
+        shrink_to: None,
+        groups: vec![],
+    };
+    let items = parse_attrs(&f.attrs, FIELD_KEYS)?;
+    check_duplicates(&items, &["group"])?;
+
+    // The key which picked how the field is cleared, if not `Clearable::clear`.
+    let mut mode: Option<&Ident> = None;
+    for item in &items {
+        let name = item.name();
+        if [
+            "skip", "default", "expr", "raw_expr", "with", "value", "zeroize",
+        ]
+        .contains(&name.as_str())
+        {
+            if let Some(mode) = mode {
+                return Err(conflict(mode, &item.key));
+            }
+            mode = Some(&item.key);
+        }
+        match (name.as_str(), &item.value) {
+            ("skip", _) => field.clear = None,
+            ("default", _) => field.clear = Some(FieldClear::Default),
+            ("zeroize", _) => field.clear = Some(FieldClear::Zeroize),
+            ("expr", AttrValue::Str(expr)) => {
+                let source = expr.value().replace("{}", place);
+                field.clear = Some(FieldClear::Expr(parse_expr(expr, &source)?));
+            }
+            ("raw_expr", AttrValue::Str(expr)) => {
+                field.clear = Some(FieldClear::RawExpr(parse_expr(expr, &expr.value())?));
+            }
+            ("with", AttrValue::Path(path)) => field.clear = Some(FieldClear::With(path.clone())),
+            ("value", AttrValue::Expr(expr)) => {
+                field.clear = Some(FieldClear::Value((**expr).clone()))
+            }
+            ("shrink_to", AttrValue::Int(limit)) => {
+                limit.base10_parse::<usize>()?;
+                field.shrink_to = Some(limit.clone());
+            }
+            ("group", AttrValue::Str(group)) => {
+                if syn::parse_str::<Ident>(&group.value()).is_err() {
+                    return Err(syn::Error::new_spanned(
+                        group,
+                        "clearable group must be an identifier",
+                    ));
+                }
+                if RESERVED_GROUPS.contains(&group.value().as_str()) {
+                    return Err(syn::Error::new_spanned(
+                        group,
+                        format!(
+                            "clearable group `{0}` would shadow `Clearable::clear_{0}`",
+                            group.value()
+                        ),
+                    ));
+                }
+                field.groups.push(group.clone());
+            }
+            _ => unreachable!("value kinds are checked while parsing"),
+        }
+    }
+
+    // `shrink_to` only applies to `Clearable::clear`, and skipped fields are never cleared.
+    if let Some(mode) = mode {
+        let ignored = items
+            .iter()
+            .find(|item| item.name() == "shrink_to" || (mode == "skip" && item.name() == "group"));
+        if let Some(item) = ignored {
+            return Err(conflict(mode, &item.key));
+        }
+    }
+
+    Ok(field)
+}
+
+/// The identifier a variant field is bound to when matching on `self`. Prefixed so fields can't
+/// shadow the parameters of the generated methods, such as `limit` or `group`.
+fn binding(member: &syn::Member) -> Ident {
+    match member {
+        syn::Member::Named(ident) => format_ident!("__field_{}", ident.unraw()),
+        syn::Member::Unnamed(index) => format_ident!("__field_{}", index.index),
+    }
+}
+
+fn members(fields: &syn::Fields) -> impl Iterator<Item = (&syn::Field, syn::Member)> {
+    fields.iter().enumerate().map(|(i, f)| {
+        let member = match &f.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(i.into()),
+        };
+        (f, member)
+    })
+}
+
+fn is_reset_variant(variant: &syn::Variant) -> syn::Result<bool> {
+    let items = parse_attrs(&variant.attrs, VARIANT_KEYS)?;
+    check_duplicates(&items, &[])?;
+    Ok(!items.is_empty())
+}
+
+/// Container level `#[clearable(...)]` attributes.
+#[derive(Default)]
+struct Options {
+    /// Replaces the inferred bounds of the `Clearable` impl when set.
+    bound: Option<Vec<syn::WherePredicate>>,
+    /// Replaces the inferred bounds of the `IsCleared` impl when set.
+    is_cleared_bound: Option<Vec<syn::WherePredicate>>,
+    /// Default `shrink_to` for every cleared field.
+    shrink_to: Option<syn::LitInt>,
+    /// Implement `Clearable::debug_assert_cleared` with `IsCleared`.
+    assert_cleared: bool,
+    /// Check in debug builds that `clear` leaves every field that isn't skipped equal to the one
+    /// from `Default::default()`.
+    check_default: bool,
+    /// Called with `&mut self` before the fields are cleared.
+    before: Option<syn::Path>,
+    /// Called with `&mut self` after the fields are cleared.
+    after: Option<syn::Path>,
+    /// From `by_name`, which generates `clear_field` and `CLEARABLE_FIELDS`. Kept for its span.
+    by_name: Option<Ident>,
+}
+
+fn parse_options(attrs: &[Attribute]) -> syn::Result<Options> {
+    let mut options = Options::default();
+    let items = parse_attrs(attrs, CONTAINER_KEYS)?;
+    check_duplicates(&items, &["bound"])?;
+    for item in items {
+        match (item.name().as_str(), item.value) {
+            ("bound", AttrValue::Bounds(bounds)) => {
+                for (target, predicates) in bounds {
+                    let predicates = predicates.parse_with(
+                        Punctuated::<syn::WherePredicate, token::Comma>::parse_terminated,
+                    )?;
+                    let bound = match target {
+                        Some(target) if target == "is_cleared" => &mut options.is_cleared_bound,
+                        _ => &mut options.bound,
+                    };
+                    bound.get_or_insert_with(Vec::new).extend(predicates);
+                }
+            }
+            ("shrink_to", AttrValue::Int(limit)) => {
+                limit.base10_parse::<usize>()?;
+                options.shrink_to = Some(limit);
+            }
+            ("assert_cleared", _) => options.assert_cleared = true,
+            ("check_default", _) => options.check_default = true,
+            ("before", AttrValue::Path(path)) => options.before = Some(path),
+            ("after", AttrValue::Path(path)) => options.after = Some(path),
+            ("by_name", _) => options.by_name = Some(item.key),
+            _ => unreachable!("value kinds are checked while parsing"),
+        }
+    }
+    Ok(options)
+}
+
+/// Whether `tokens` mention any of the type parameters.
+fn uses_params(tokens: TokenStream, params: &HashSet<Ident>) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(ident) => params.contains(&ident),
+        TokenTree::Group(group) => uses_params(group.stream(), params),
+        _ => false,
+    })
+}
+
+enum Data {
+    Struct(Vec<Field>),
+    Enum {
+        variant: Ident,
+        fields: Vec<Field>,
+        /// The other variants, which are replaced by `variant` when cleared.
+        others: Vec<Ident>,
+    },
+}
+
 pub struct Top {
-    abga: Option<AngleBracketedGenericArguments>,
+    options: Options,
     ident: Ident,
-    where_clause: Option<syn::WhereClause>,
-    fields: Vec<Ident>,
-    default_fields: Vec<Ident>,
-    expressions: Vec<syn::Expr>,
+    generics: syn::Generics,
+    data: Data,
 }
 
 impl Parse for Top {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let _attrs = input.call(Attribute::parse_outer)?;
+        let attrs = input.call(Attribute::parse_outer)?;
+        let options = parse_options(&attrs)?;
         let _vis = input.parse::<Visibility>()?;
 
-        if !input.peek(token::Struct) {
-            return Err(input.error("Only structs are supported"));
-        }
-        input.parse::<token::Struct>()?;
+        let is_enum = if input.peek(token::Enum) {
+            input.parse::<token::Enum>()?;
+            true
+        } else if input.peek(token::Struct) {
+            input.parse::<token::Struct>()?;
+            false
+        } else {
+            return Err(input.error("Only structs and enums are supported"));
+        };
         let ident = input.parse::<Ident>()?;
 
-        let abga = if input.peek(token::Lt) {
-            Some(input.parse::<AngleBracketedGenericArguments>()?)
+        let mut generics = input.parse::<syn::Generics>()?;
+        generics.where_clause = input.parse()?;
+
+        let data = if is_enum {
+            if let Some(by_name) = &options.by_name {
+                return Err(syn::Error::new_spanned(
+                    by_name,
+                    "clearable(by_name) is only supported on structs",
+                ));
+            }
+            let content;
+            syn::braced!(content in input);
+            let variants =
+                content.parse_terminated::<syn::Variant, token::Comma>(syn::Variant::parse)?;
+            parse_enum(&ident, variants)?
         } else {
-            None
+            let fields = if input.peek(token::Brace) {
+                syn::Fields::Named(input.parse::<syn::FieldsNamed>()?)
+            } else if input.peek(token::Paren) {
+                let fields = syn::Fields::Unnamed(input.parse::<syn::FieldsUnnamed>()?);
+                generics.where_clause = input.parse()?;
+                input.parse::<token::Semi>()?;
+                fields
+            } else {
+                input.parse::<token::Semi>()?;
+                syn::Fields::Unit
+            };
+            Data::Struct(
+                members(&fields)
+                    .map(|(f, member)| {
+                        let place = format!("self.{}", member.to_token_stream());
+                        parse_field(f, member, &place)
+                    })
+                    .collect::<syn::Result<_>>()?,
+            )
         };
 
-        let where_clause = input.parse::<syn::WhereClause>().ok();
+        Ok(Self {
+            options,
+            ident,
+            generics,
+            data,
+        })
+    }
+}
 
-        if !input.peek(token::Brace) {
-            return Err(input.error("Only structs with named fields are supported"));
+fn parse_enum(
+    ident: &Ident,
+    variants: Punctuated<syn::Variant, token::Comma>,
+) -> syn::Result<Data> {
+    let mut reset: Option<&syn::Variant> = None;
+    for variant in variants.iter() {
+        if is_reset_variant(variant)? {
+            if reset.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &variant.ident,
+                    "Only one variant can be marked with clearable(reset)",
+                ));
+            }
+            reset = Some(variant);
         }
+    }
+    let variant = reset.ok_or_else(|| {
+        syn::Error::new_spanned(
+            ident,
+            "Enums must mark the variant to reset to with #[clearable(reset)]",
+        )
+    })?;
+    // Fields of the other variants are dropped, never cleared, so their attributes would be ignored.
+    let other_fields = variants
+        .iter()
+        .filter(|v| v.ident != variant.ident)
+        .flat_map(|v| &v.fields);
+    for attr in other_fields.flat_map(|f| &f.attrs) {
+        if attr.path.is_ident("clearable") {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "clearable field attributes are only allowed in the clearable(reset) variant",
+            ));
+        }
+    }
+    let fields = members(&variant.fields)
+        .map(|(f, member)| {
+            let place = format!("(*{})", binding(&member));
+            parse_field(f, member, &place)
+        })
+        .collect::<syn::Result<_>>()?;
+    let others = variants
+        .iter()
+        .filter(|v| v.ident != variant.ident)
+        .map(|v| v.ident.clone())
+        .collect();
+    Ok(Data::Enum {
+        variant: variant.ident.clone(),
+        fields,
+        others,
+    })
+}
+
+impl Top {
+    /// `FieldTy: Clearable` for cleared fields and `FieldTy: Default` for defaulted ones, but only
+    /// for field types which mention a type parameter.
+    fn inferred_bounds(&self) -> Vec<syn::WherePredicate> {
+        self.inferred_bounds_with(|field| {
+            let ty = &field.ty;
+            let mut bounds: Vec<syn::WherePredicate> = vec![];
+            match field.clear {
+                Some(FieldClear::Clear) => {
+                    bounds.push(syn::parse_quote!(#ty: ::kmacros::Clearable));
+                }
+                Some(FieldClear::Zeroize) => {
+                    bounds.push(syn::parse_quote!(#ty: ::kmacros::SecureClearable));
+                }
+                _ => {}
+            }
+            // Enums construct the reset variant from scratch, so every field needs a default.
+            if matches!(field.clear, Some(FieldClear::Default))
+                || matches!(self.data, Data::Enum { .. })
+            {
+                bounds.push(syn::parse_quote!(#ty: Default));
+            }
+            if self.options.check_default && field.clear.is_some() {
+                bounds.push(syn::parse_quote!(#ty: PartialEq));
+            }
+            bounds
+        })
+    }
+
+    /// Like `inferred_bounds`, but for the checks done by `IsCleared`.
+    fn inferred_is_cleared_bounds(&self) -> Vec<syn::WherePredicate> {
+        self.inferred_bounds_with(|field| {
+            let ty = &field.ty;
+            match field.clear {
+                Some(FieldClear::Clear) => vec![syn::parse_quote!(#ty: ::kmacros::IsCleared)],
+                Some(FieldClear::Default) => vec![syn::parse_quote!(#ty: Default + PartialEq)],
+                _ => vec![],
+            }
+        })
+    }
 
-        let raw_fields = input.parse::<syn::FieldsNamed>()?;
-        let mut default_fields = vec![];
-        let mut expressions = vec![];
-        let fields = raw_fields
-            .named
+    fn inferred_bounds_with(
+        &self,
+        bounds_for: impl Fn(&Field) -> Vec<syn::WherePredicate>,
+    ) -> Vec<syn::WherePredicate> {
+        let params = self
+            .generics
+            .type_params()
+            .map(|param| param.ident.clone())
+            .collect::<HashSet<_>>();
+        let fields = match &self.data {
+            Data::Struct(fields) => fields,
+            Data::Enum { fields, .. } => fields,
+        };
+        fields
             .iter()
-            .filter_map(|f| {
-                let field = f.ident.as_ref()?;
-                for attr in &f.attrs {
-                    if attr.path.is_ident("clearable") {
-                        match attr.parse_meta().expect("invalid clearable attr") {
-                            syn::Meta::List(list) => {
-                                // In field_iter($(meta),+) now.
-                                for meta in list.nested.iter() {
-                                    match meta {
-                                        syn::NestedMeta::Meta(syn::Meta::Path(p))
-                                            if p.is_ident("skip") =>
-                                        {
-                                            return None;
-                                        }
-                                        syn::NestedMeta::Meta(syn::Meta::Path(p))
-                                            if p.is_ident("default") =>
-                                        {
-                                            default_fields.push(field.clone());
-                                            return None;
-                                        }
-                                        syn::NestedMeta::Meta(syn::Meta::NameValue(
-                                            syn::MetaNameValue {
-                                                path,
-                                                eq_token: _,
-                                                lit: syn::Lit::Str(expr),
-                                            },
-                                        )) if path.is_ident("expr") => {
-                                            let expr = expr
-                                                .value()
-                                                .replace("{}", &format!("self.{}", field));
-                                            let expr: syn::Expr =
-                                                syn::parse_str(&expr).expect("Invalid expression");
-                                            expressions.push(expr);
-                                            return None;
-                                        }
-                                        syn::NestedMeta::Meta(syn::Meta::NameValue(
-                                            syn::MetaNameValue {
-                                                path,
-                                                eq_token: _,
-                                                lit: syn::Lit::Str(expr),
-                                            },
-                                        )) if path.is_ident("raw_expr") => {
-                                            let expr: syn::Expr = syn::parse_str(&expr.value())
-                                                .expect("Invalid expression");
-                                            expressions.push(expr);
-                                            return None;
-                                        }
-                                        syn::NestedMeta::Meta(meta)
-                                            if meta.path().is_ident("skip") =>
-                                        {
-                                            panic!(
-                                                "Invalid skip attr: {}\n\
-                                                Only clearable(skip) is valid",
-                                                meta.into_token_stream()
-                                            )
-                                        }
-                                        syn::NestedMeta::Meta(meta)
-                                            if meta.path().is_ident("default") =>
-                                        {
-                                            panic!(
-                                                "Invalid skip attr: {}\n\
-                                                Only clearable(default) is valid",
-                                                meta.into_token_stream()
-                                            )
-                                        }
-                                        meta => {
-                                            panic!(
-                                                "Invalid clearable attr: {}",
-                                                meta.into_token_stream()
-                                            )
-                                        }
-                                    }
-                                }
-                            }
-                            meta => {
-                                panic!(
-                                    "Expected a list of meta attrs for clearable: {}",
-                                    meta.into_token_stream()
-                                )
-                            }
-                        }
+            .filter(|field| uses_params(field.ty.to_token_stream(), &params))
+            .flat_map(bounds_for)
+            .collect()
+    }
+
+    /// `generics` with `bound`, from the attribute, or the inferred bounds added to the where
+    /// clause.
+    fn bounded_generics(
+        &self,
+        bound: &Option<Vec<syn::WherePredicate>>,
+        inferred: impl FnOnce() -> Vec<syn::WherePredicate>,
+    ) -> syn::Generics {
+        let mut generics = self.generics.clone();
+        let bounds = match bound {
+            Some(bound) => bound.clone(),
+            None => inferred(),
+        };
+        generics.make_where_clause().predicates.extend(bounds);
+        generics
+    }
+
+    /// The `IsCleared` impl.
+    pub fn is_cleared_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let generics = self.bounded_generics(&self.options.is_cleared_bound, || {
+            self.inferred_is_cleared_bounds()
+        });
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let checks = |fields: &[Field], place: &dyn Fn(&Field) -> TokenStream| {
+            let checks = fields.iter().filter_map(|f| {
+                let place = place(f);
+                let ty = &f.ty;
+                match f.clear.as_ref()? {
+                    FieldClear::Clear => Some(quote! {
+                        ::kmacros::IsCleared::is_cleared(&#place)
+                    }),
+                    FieldClear::Default => Some(quote! {
+                        #place == <#ty as Default>::default()
+                    }),
+                    FieldClear::Expr(_)
+                    | FieldClear::RawExpr(_)
+                    | FieldClear::With(_)
+                    | FieldClear::Value(_)
+                    | FieldClear::Zeroize => None,
+                }
+            });
+            quote!(true #(&& #checks)*)
+        };
+        let body = match &self.data {
+            Data::Struct(fields) => checks(fields, &|f| {
+                let member = &f.member;
+                quote!(self.#member)
+            }),
+            Data::Enum {
+                variant, fields, ..
+            } => {
+                let pattern = variant_pattern(variant, fields);
+                let checks = checks(fields, &|f| {
+                    let binding = binding(&f.member);
+                    quote!((*#binding))
+                });
+                quote! {
+                    match self {
+                        #[allow(unused_variables)]
+                        #pattern => #checks,
+                        _ => false,
                     }
                 }
+            }
+        };
 
-                Some(field.clone())
-            })
-            .collect::<Vec<_>>();
+        quote! {
+            impl #impl_generics ::kmacros::IsCleared for #ident #ty_generics #where_clause {
+                fn is_cleared(&self) -> bool {
+                    #body
+                }
+            }
+        }
+    }
+}
 
-        Ok(Self {
-            abga,
-            ident,
-            where_clause,
-            fields,
-            default_fields,
-            expressions,
+impl Top {
+    /// Statements clearing `fields`, where `place` is the place expression for a field. Inside
+    /// `clear_and_shrink_to`, `limit` is its argument.
+    fn clear_fields<'a>(
+        &self,
+        fields: impl IntoIterator<Item = &'a Field>,
+        place: impl Fn(&'a Field) -> TokenStream,
+        limit: Option<&Ident>,
+    ) -> TokenStream {
+        let fields = fields.into_iter().collect::<Vec<_>>();
+        let clears = fields
+            .iter()
+            .filter(|f| matches!(f.clear, Some(FieldClear::Clear)))
+            .map(|f| {
+                let place = place(f);
+                let shrink_to = f.shrink_to.as_ref().or(self.options.shrink_to.as_ref());
+                match (limit, shrink_to) {
+                    (None, None) => quote! {
+                        ::kmacros::Clearable::clear(&mut #place);
+                    },
+                    (None, Some(n)) => quote! {
+                        ::kmacros::Clearable::clear_and_shrink_to(&mut #place, #n);
+                    },
+                    (Some(limit), None) => quote! {
+                        ::kmacros::Clearable::clear_and_shrink_to(&mut #place, #limit);
+                    },
+                    (Some(limit), Some(n)) => quote! {
+                        ::kmacros::Clearable::clear_and_shrink_to(
+                            &mut #place,
+                            ::core::cmp::min(#limit, #n),
+                        );
+                    },
+                }
+            });
+        let defaults = fields
+            .iter()
+            .filter(|f| matches!(f.clear, Some(FieldClear::Default)))
+            .map(|f| place(f));
+        let expressions = fields.iter().filter_map(|f| match &f.clear {
+            Some(FieldClear::Expr(expr) | FieldClear::RawExpr(expr)) => Some(quote!(#expr)),
+            Some(FieldClear::With(path)) => {
+                let place = place(f);
+                Some(quote!(#path(&mut #place)))
+            }
+            Some(FieldClear::Value(expr)) => {
+                let place = place(f);
+                Some(quote!(#place = #expr))
+            }
+            Some(FieldClear::Zeroize) => {
+                let place = place(f);
+                Some(quote!(::kmacros::SecureClearable::secure_clear(&mut #place)))
+            }
+            _ => None,
+        });
+        quote! {
+            #(#clears)*
+            #(#defaults = Default::default();)*
+            #(#expressions;)*
+        }
+    }
+
+    /// Body of `clear_group`: fields in `group` are cleared completely, and every other cleared
+    /// field gets the group forwarded so nested structs can clear their part of it.
+    fn clear_group<'a>(
+        &self,
+        fields: &'a [Field],
+        place: impl Fn(&'a Field) -> TokenStream + Copy,
+        group: &Ident,
+    ) -> TokenStream {
+        let forward = |except: Option<&str>| {
+            let places = fields
+                .iter()
+                .filter(|f| matches!(f.clear, Some(FieldClear::Clear)))
+                .filter(|f| !except.is_some_and(|name| f.in_group(name)))
+                .map(place);
+            quote! {
+                #(::kmacros::Clearable::clear_group(&mut #places, #group);)*
+            }
+        };
+        let arms = group_names(fields).into_iter().map(|name| {
+            let clears =
+                self.clear_fields(fields.iter().filter(|f| f.in_group(&name)), place, None);
+            let forward = forward(Some(&name));
+            quote! {
+                #name => {
+                    #clears
+                    #forward
+                }
+            }
+        });
+        let forward = forward(None);
+        quote! {
+            match #group {
+                #(#arms)*
+                _ => {
+                    #forward
+                }
+            }
+        }
+    }
+
+    /// Body of `clear_with_report`: cleared fields report under their name, limited by their
+    /// `shrink_to`, and the other fields are reset as in `clear`.
+    fn report_fields<'a>(
+        &self,
+        fields: &'a [Field],
+        place: impl Fn(&'a Field) -> TokenStream + Copy,
+        report: &Ident,
+    ) -> TokenStream {
+        let reports = fields
+            .iter()
+            .filter(|f| matches!(f.clear, Some(FieldClear::Clear)))
+            .map(|f| {
+                let place = place(f);
+                let name = f.name();
+                let enter = match f.shrink_to.as_ref().or(self.options.shrink_to.as_ref()) {
+                    Some(n) => quote!(#report.enter_with_limit(#name, #n);),
+                    None => quote!(#report.enter(#name);),
+                };
+                quote! {
+                    #enter
+                    ::kmacros::Clearable::clear_with_report(&mut #place, #report);
+                    #report.leave();
+                }
+            });
+        let rest = self.clear_fields(
+            fields
+                .iter()
+                .filter(|f| !matches!(f.clear, Some(FieldClear::Clear))),
+            place,
+            None,
+        );
+        quote! {
+            #(#reports)*
+            #rest
+        }
+    }
+
+    /// Maximum `retained_capacity` of the cleared fields.
+    fn retained_capacity<'a>(
+        &self,
+        fields: &'a [Field],
+        place: impl Fn(&'a Field) -> TokenStream,
+    ) -> TokenStream {
+        let places = fields
+            .iter()
+            .filter(|f| matches!(f.clear, Some(FieldClear::Clear)))
+            .map(place);
+        quote! {
+            0usize #(.max(::kmacros::Clearable::retained_capacity(&#places)))*
+        }
+    }
+
+    /// Body of `clear_field`: the first segment of the dotted `name` picks a field, which is
+    /// cleared as in `clear`, and the rest is forwarded to it.
+    fn clear_field<'a>(
+        &self,
+        fields: &'a [Field],
+        place: impl Fn(&'a Field) -> TokenStream + Copy,
+        name: &Ident,
+    ) -> TokenStream {
+        let arms = fields.iter().filter(|f| f.clear.is_some()).map(|f| {
+            let field_name = f.name();
+            let clear = self.clear_fields(std::iter::once(f), place, None);
+            let nested = matches!(f.clear, Some(FieldClear::Clear)).then(|| {
+                let place = place(f);
+                quote! {
+                    (#field_name, Some(rest)) => ::kmacros::Clearable::clear_field(&mut #place, rest),
+                }
+            });
+            quote! {
+                (#field_name, None) => {
+                    #clear
+                    true
+                }
+                #nested
+            }
+        });
+        quote! {
+            let (head, rest) = match #name.split_once('.') {
+                Some((head, rest)) => (head, Some(rest)),
+                None => (#name, None),
+            };
+            match (head, rest) {
+                #(#arms)*
+                _ => false,
+            }
+        }
+    }
+}
+
+impl Field {
+    fn in_group(&self, name: &str) -> bool {
+        self.groups.iter().any(|g| g.value() == name)
+    }
+
+    /// The field name used in report paths and by `clear_field`, the index for tuple structs.
+    fn name(&self) -> String {
+        match &self.member {
+            syn::Member::Named(ident) => ident.unraw().to_string(),
+            syn::Member::Unnamed(index) => index.index.to_string(),
+        }
+    }
+}
+
+/// Distinct `group` names of `fields`, in order of appearance.
+fn group_names(fields: &[Field]) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    for group in fields.iter().flat_map(|f| &f.groups) {
+        if !names.contains(&group.value()) {
+            names.push(group.value());
+        }
+    }
+    names
+}
+
+impl Top {
+    /// Debug assertions that the fields which aren't skipped equal those of `Self::default()`.
+    fn check_default(&self) -> TokenStream {
+        let ident = &self.ident;
+        let check = |f: &Field, place: TokenStream, default: TokenStream| {
+            let message = format!(
+                "{}::clear left `{}` different from Default::default()",
+                ident,
+                f.member.to_token_stream()
+            );
+            quote!(debug_assert!(#place == #default, #message);)
+        };
+        let checks = match &self.data {
+            Data::Struct(fields) => {
+                let checks = fields.iter().filter(|f| f.clear.is_some()).map(|f| {
+                    let member = &f.member;
+                    check(f, quote!(self.#member), quote!(__default.#member))
+                });
+                quote!(#(#checks)*)
+            }
+            Data::Enum {
+                variant, fields, ..
+            } => {
+                let fields = fields
+                    .iter()
+                    .filter(|f| f.clear.is_some())
+                    .collect::<Vec<_>>();
+                let members = fields.iter().map(|f| &f.member).collect::<Vec<_>>();
+                let bindings = fields
+                    .iter()
+                    .map(|f| format_ident!("__cleared_{}", f.name()))
+                    .collect::<Vec<_>>();
+                let defaults = fields
+                    .iter()
+                    .map(|f| format_ident!("__default_{}", f.name()))
+                    .collect::<Vec<_>>();
+                let checks = fields
+                    .iter()
+                    .zip(bindings.iter().zip(&defaults))
+                    .map(|(f, (b, d))| check(f, quote!(#b), quote!(#d)));
+                let message = format!(
+                    "{}::default() isn't the clearable(reset) variant {}",
+                    ident, variant
+                );
+                quote! {
+                    match (&*self, &__default) {
+                        (
+                            Self::#variant { #(#members: #bindings,)* .. },
+                            Self::#variant { #(#members: #defaults,)* .. },
+                        ) => {
+                            #(#checks)*
+                        }
+                        _ => debug_assert!(false, #message),
+                    }
+                }
+            }
+        };
+        quote! {
+            #[cfg(debug_assertions)]
+            {
+                let __default: Self = Default::default();
+                #checks
+            }
+        }
+    }
+}
+
+/// Pattern which binds the fields of `variant` used by `clear` to their `binding`.
+fn variant_pattern(variant: &Ident, fields: &[Field]) -> TokenStream {
+    let patterns = fields
+        .iter()
+        .filter(|f| {
+            matches!(
+                f.clear,
+                Some(
+                    FieldClear::Clear
+                        | FieldClear::Default
+                        | FieldClear::Expr(_)
+                        | FieldClear::With(_)
+                        | FieldClear::Value(_)
+                        | FieldClear::Zeroize
+                )
+            )
         })
+        .map(|f| {
+            let member = &f.member;
+            let binding = binding(member);
+            quote!(#member: #binding)
+        });
+    quote! {
+        Self::#variant { #(#patterns,)* .. }
     }
 }
 
 impl ToTokens for Top {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        use quote::quote;
         let Top {
-            abga,
+            options,
             ident,
-            where_clause,
-            fields,
-            default_fields,
-            expressions,
+            data,
+            ..
         } = self;
 
+        let mut generics = self.bounded_generics(&self.options.bound, || self.inferred_bounds());
+        let debug_assert_cleared = options.assert_cleared.then(|| {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(Self: ::kmacros::IsCleared));
+            let message = format!("{} was not cleared", ident);
+            quote! {
+                fn debug_assert_cleared(&self) {
+                    debug_assert!(::kmacros::IsCleared::is_cleared(self), #message);
+                }
+            }
+        });
+        let check_default = options.check_default.then(|| {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(Self: Default));
+            self.check_default()
+        });
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let limit = format_ident!("limit");
+        let group = format_ident!("group");
+        let report = format_ident!("report");
+        let name = format_ident!("name");
+        let (clear, clear_and_shrink_to, retained_capacity, clear_group, clear_with_report) =
+            match data {
+                Data::Struct(fields) => {
+                    let place = |f: &Field| {
+                        let member = &f.member;
+                        quote!(self.#member)
+                    };
+                    (
+                        self.clear_fields(fields, place, None),
+                        self.clear_fields(fields, place, Some(&limit)),
+                        self.retained_capacity(fields, place),
+                        self.clear_group(fields, place, &group),
+                        self.report_fields(fields, place, &report),
+                    )
+                }
+                Data::Enum {
+                    variant,
+                    fields,
+                    others,
+                } => {
+                    let place = |f: &Field| {
+                        let binding = binding(&f.member);
+                        quote!((*#binding))
+                    };
+                    let all_members = fields.iter().map(|f| &f.member);
+                    let reset = quote! {
+                        if !matches!(self, Self::#variant { .. }) {
+                            *self = Self::#variant { #(#all_members: Default::default(),)* };
+                        }
+                    };
+                    let pattern = variant_pattern(variant, fields);
+                    let in_place = |clears: TokenStream| {
+                        if clears.is_empty() {
+                            reset.clone()
+                        } else {
+                            quote! {
+                                #reset
+                                if let #pattern = self {
+                                    #clears
+                                }
+                            }
+                        }
+                    };
+                    let capacity = self.retained_capacity(fields, place);
+                    let clear_group = self.clear_group(fields, place, &group);
+                    // Switching variants drops the fields of the replaced one, which may not be
+                    // `Clearable`, so only the switch itself is recorded.
+                    let record_replaced = if others.is_empty() {
+                        quote!()
+                    } else {
+                        let names = others.iter().map(|ident| ident.unraw().to_string());
+                        quote! {
+                            match self {
+                                #(Self::#others { .. } => #report.record_replaced(#names),)*
+                                _ => {}
+                            }
+                        }
+                    };
+                    let clear_with_report = in_place(self.report_fields(fields, place, &report));
+                    (
+                        in_place(self.clear_fields(fields, place, None)),
+                        in_place(self.clear_fields(fields, place, Some(&limit))),
+                        quote! {
+                            match self {
+                                #[allow(unused_variables)]
+                                #pattern => #capacity,
+                                _ => 0,
+                            }
+                        },
+                        // Groups only apply while in the reset variant.
+                        quote! {
+                            #[allow(unused_variables)]
+                            if let #pattern = self {
+                                #clear_group
+                            }
+                        },
+                        quote! {
+                            #record_replaced
+                            #clear_with_report
+                        },
+                    )
+                }
+            };
+
+        let clear_field = match data {
+            Data::Struct(fields) if options.by_name.is_some() => {
+                let body = self.clear_field(
+                    fields,
+                    |f: &Field| {
+                        let member = &f.member;
+                        quote!(self.#member)
+                    },
+                    &name,
+                );
+                Some(quote! {
+                    fn clear_field(&mut self, #name: &str) -> bool {
+                        #body
+                    }
+                })
+            }
+            _ => None,
+        };
+
+        let before = options.before.as_ref().map(|path| quote!(#path(self);));
+        let after = options.after.as_ref().map(|path| quote!(#path(self);));
+        let (clear, clear_and_shrink_to, clear_with_report) = (
+            quote!(#before #clear #after #check_default),
+            quote!(#before #clear_and_shrink_to #after),
+            quote!(#before #clear_with_report #after),
+        );
+
         quote! {
-            impl #abga ::kmacros::Clearable for #ident #abga #where_clause {
+            impl #impl_generics ::kmacros::Clearable for #ident #ty_generics #where_clause {
                 fn clear(&mut self) {
-                    #(::kmacros::Clearable::clear(&mut self.#fields);)*
-                    #(self.#default_fields = Default::default();)*
-                    #(#expressions;)*
+                    #clear
                 }
+
+                #[allow(unused_variables)]
+                fn clear_and_shrink_to(&mut self, #limit: usize) {
+                    #clear_and_shrink_to
+                }
+
+                fn retained_capacity(&self) -> usize {
+                    #retained_capacity
+                }
+
+                #[allow(unused_variables)]
+                fn clear_group(&mut self, #group: &str) {
+                    #clear_group
+                }
+
+                #[allow(unused_variables)]
+                fn clear_with_report(&mut self, #report: &mut ::kmacros::ClearReport) {
+                    #clear_with_report
+                }
+
+                #clear_field
+
+                #debug_assert_cleared
             }
         }
         .to_tokens(tokens);
+
+        let fields = match data {
+            Data::Struct(fields) => fields,
+            Data::Enum { fields, .. } => fields,
+        };
+        let group_fns = group_names(fields).into_iter().map(|name| {
+            let doc = format!("Clears the fields in the `{}` group.", name);
+            let method = format_ident!("clear_{}", name);
+            quote! {
+                #[doc = #doc]
+                pub fn #method(&mut self) {
+                    ::kmacros::Clearable::clear_group(self, #name);
+                }
+            }
+        });
+        let field_names = options.by_name.is_some().then(|| {
+            let names = fields.iter().filter(|f| f.clear.is_some()).map(Field::name);
+            quote! {
+                /// Names accepted by `Clearable::clear_field`, without the nested paths.
+                pub const CLEARABLE_FIELDS: &'static [&'static str] = &[#(#names),*];
+            }
+        });
+        if field_names.is_some() || !fields.iter().all(|f| f.groups.is_empty()) {
+            quote! {
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    #field_names
+                    #(#group_fns)*
+                }
+            }
+            .to_tokens(tokens);
+        }
     }
 }
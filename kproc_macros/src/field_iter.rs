@@ -7,7 +7,8 @@
 //! #[derive(Debug, FieldIter)]
 //! /// outer
 //! #[allow(dead_code)]
-//! #[field_iter(debug_iter = "dyn Debug")]
+//! #[field_iter(debug_iter = "dyn std::fmt::Debug")]
+//! #[field_iter(bound(debug_iter = "T: std::fmt::Debug"))]
 //! struct Foo<T> {
 //!    x: bool,
 //!    b: String,
@@ -21,39 +22,74 @@
 //!     t: 64u64,
 //! }.debug_iter(|name, value| {
 //!   println!("{name} = {value:?}");
-//!   None
+//!   None::<()>
+//! });
+//! ```
+//!
+//! Enums get the same methods, which visit the fields of the active variant. Fields of tuple
+//! variants are named by their index, and `variant_name()` returns the name of the variant.
+//!
+//! ```rust
+//! # use kmacros::FieldIter;
+//! #[derive(FieldIter)]
+//! #[field_iter(debug_iter = "dyn std::fmt::Debug")]
+//! enum Event {
+//!     Click { x: i32, y: i32 },
+//!     Key(char),
+//!     Close,
+//! }
+//!
+//! let event = Event::Key('q');
+//! event.debug_iter(|name, value| {
+//!     println!("{}.{name} = {value:?}", event.variant_name());
+//!     None::<()>
 //! });
 //! ```
 use proc_macro2::Ident;
-use quote::ToTokens;
+use quote::{format_ident, ToTokens};
 use std::collections::HashSet;
-use syn::{
-    parse::{Parse, ParseStream},
-    token, AngleBracketedGenericArguments, Attribute, LitStr, Visibility,
-};
-
-struct Doc(pub String);
-impl Parse for Doc {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        input.parse::<token::Eq>()?;
-        let s = input.parse::<LitStr>()?.value();
-        Ok(Doc(s.trim_start().to_string()))
-    }
-}
+use syn::{parse::Parse, token, AngleBracketedGenericArguments, Attribute, Visibility};
 
 /// A function to generate an iterator for
 pub struct FunctionJob {
     name: Ident,
     ty: syn::Type,
-    fields_to_skip: HashSet<Ident>,
+    /// Indices into `Top::fields`.
+    fields_to_skip: HashSet<usize>,
     predicates: Vec<syn::WherePredicate>,
 }
 
+struct Variant {
+    ident: Ident,
+    /// Indices into `Top::fields`.
+    fields: Vec<usize>,
+}
+
+enum Data {
+    Struct,
+    Enum(Vec<Variant>),
+}
+
 pub struct Top {
     abga: Option<AngleBracketedGenericArguments>,
     ident: Ident,
     functions: Vec<FunctionJob>,
-    fields: Vec<Ident>,
+    /// Fields of the struct, or of every variant in order.
+    fields: Vec<syn::Member>,
+    data: Data,
+}
+
+/// The name a field is passed to the callback with, its index for tuple variants.
+fn field_name(member: &syn::Member) -> String {
+    match member {
+        syn::Member::Named(ident) => ident.to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// The identifier a variant field is bound to when matching on `self`.
+fn binding(member: &syn::Member) -> Ident {
+    format_ident!("__{}", field_name(member).trim_start_matches("r#"))
 }
 
 impl Parse for Top {
@@ -61,10 +97,15 @@ impl Parse for Top {
         let attrs = input.call(Attribute::parse_outer)?;
         let _vis = input.parse::<Visibility>()?;
 
-        if !input.peek(token::Struct) {
-            return Err(input.error("Only structs are supported"));
-        }
-        input.parse::<token::Struct>()?;
+        let is_enum = if input.peek(token::Enum) {
+            input.parse::<token::Enum>()?;
+            true
+        } else if input.peek(token::Struct) {
+            input.parse::<token::Struct>()?;
+            false
+        } else {
+            return Err(input.error("Only structs and enums are supported"));
+        };
         let ident = input.parse::<Ident>()?;
 
         let abga = if input.peek(token::Lt) {
@@ -81,7 +122,7 @@ impl Parse for Top {
 
         let mut functions: Vec<FunctionJob> = vec![];
 
-        fn parse_bound_meta(functions: &mut Vec<FunctionJob>, m: &syn::MetaList) {
+        fn parse_bound_meta(functions: &mut [FunctionJob], m: &syn::MetaList) {
             // field_iter(bound($(bound_meta),*))
             for bound_meta in m.nested.iter() {
                 match bound_meta {
@@ -91,14 +132,15 @@ impl Parse for Top {
                         lit: syn::Lit::Str(predicate),
                     })) => {
                         let target_fn = target_fn.get_ident().expect("Expected an ident for bound");
-                        let func =
-                            functions
-                                .iter_mut()
-                                .find(|f| *target_fn == f.name)
-                                .expect(&format!(
+                        let func = functions
+                            .iter_mut()
+                            .find(|f| *target_fn == f.name)
+                            .unwrap_or_else(|| {
+                                panic!(
                                     "Not a generated function: {}",
                                     target_fn.into_token_stream()
-                                ));
+                                )
+                            });
                         func.predicates
                             .push(predicate.parse().expect("Invalid predicate"));
                     }
@@ -109,7 +151,7 @@ impl Parse for Top {
             }
         }
 
-        fn parse_skip_meta(functions: &mut Vec<FunctionJob>, m: &syn::MetaList, field: &Ident) {
+        fn parse_skip_meta(functions: &mut [FunctionJob], m: &syn::MetaList, field: usize) {
             // field_iter(skip($(fn_skip),*))
             for fn_skip in m.nested.iter() {
                 match fn_skip {
@@ -118,11 +160,10 @@ impl Parse for Top {
                         let func = functions
                             .iter_mut()
                             .find(|f| *skip == f.name)
-                            .expect(&format!(
-                                "Not a generated function: {}",
-                                p.into_token_stream()
-                            ));
-                        func.fields_to_skip.insert(field.clone());
+                            .unwrap_or_else(|| {
+                                panic!("Not a generated function: {}", p.into_token_stream())
+                            });
+                        func.fields_to_skip.insert(field);
                     }
                     meta => {
                         panic!("Invalid skip target {}", meta.into_token_stream())
@@ -158,7 +199,7 @@ impl Parse for Top {
                                 parse_bound_meta(&mut functions, m);
                             }
                             x => {
-                                return Err(input.error(&format!(
+                                return Err(input.error(format!(
                                     "Invalid field_iter attr: {}",
                                     x.into_token_stream()
                                 )))
@@ -171,75 +212,175 @@ impl Parse for Top {
             }
         }
 
-        let raw_fields = input.parse::<syn::FieldsNamed>()?;
-        let fields = raw_fields
-            .named
-            .iter()
-            .filter_map(|f| {
-                let field = f.ident.as_ref()?;
-                for attr in &f.attrs {
-                    if attr.path.is_ident("field_iter") {
-                        match attr.parse_meta().expect("invalid field_iter attr") {
-                            syn::Meta::List(list) => {
-                                // In field_iter($(meta),+) now.
-                                for meta in list.nested.iter() {
-                                    match meta {
-                                        syn::NestedMeta::Meta(syn::Meta::List(m))
-                                            if m.path.is_ident("skip") =>
-                                        {
-                                            parse_skip_meta(&mut functions, m, field);
-                                        }
-                                        syn::NestedMeta::Meta(syn::Meta::List(m))
-                                            if m.path.is_ident("bound") =>
-                                        {
-                                            parse_bound_meta(&mut functions, m);
-                                        }
-                                        syn::NestedMeta::Meta(meta)
-                                            if meta.path().is_ident("bound") =>
-                                        {
-                                            panic!(
-                                                "Invalid bound attr: {}\n\
-                                                       Expected a bound(fn = \"T: ...\", ...) list",
-                                                meta.into_token_stream()
-                                            )
-                                        }
-                                        syn::NestedMeta::Meta(meta)
-                                            if meta.path().is_ident("skip") =>
-                                        {
-                                            panic!(
-                                                "Invalid skip attr: {}\n\
-                                                       Expected a skip(fn, ...) list",
-                                                meta.into_token_stream()
-                                            )
-                                        }
-                                        meta => {
-                                            panic!(
-                                                "Invalid field_iter attr: {}",
-                                                meta.into_token_stream()
-                                            )
-                                        }
+        fn parse_field_attrs(functions: &mut [FunctionJob], attrs: &[Attribute], field: usize) {
+            for attr in attrs {
+                if attr.path.is_ident("field_iter") {
+                    match attr.parse_meta().expect("invalid field_iter attr") {
+                        syn::Meta::List(list) => {
+                            // In field_iter($(meta),+) now.
+                            for meta in list.nested.iter() {
+                                match meta {
+                                    syn::NestedMeta::Meta(syn::Meta::List(m))
+                                        if m.path.is_ident("skip") =>
+                                    {
+                                        parse_skip_meta(functions, m, field);
+                                    }
+                                    syn::NestedMeta::Meta(syn::Meta::List(m))
+                                        if m.path.is_ident("bound") =>
+                                    {
+                                        parse_bound_meta(functions, m);
+                                    }
+                                    syn::NestedMeta::Meta(meta)
+                                        if meta.path().is_ident("bound") =>
+                                    {
+                                        panic!(
+                                            "Invalid bound attr: {}\n\
+                                                   Expected a bound(fn = \"T: ...\", ...) list",
+                                            meta.into_token_stream()
+                                        )
+                                    }
+                                    syn::NestedMeta::Meta(meta) if meta.path().is_ident("skip") => {
+                                        panic!(
+                                            "Invalid skip attr: {}\n\
+                                                   Expected a skip(fn, ...) list",
+                                            meta.into_token_stream()
+                                        )
+                                    }
+                                    meta => {
+                                        panic!(
+                                            "Invalid field_iter attr: {}",
+                                            meta.into_token_stream()
+                                        )
                                     }
                                 }
                             }
-                            meta => {
-                                panic!(
-                                    "Expected a list of meta attrs for field_iter: {}",
-                                    meta.into_token_stream()
-                                )
-                            }
+                        }
+                        meta => {
+                            panic!(
+                                "Expected a list of meta attrs for field_iter: {}",
+                                meta.into_token_stream()
+                            )
                         }
                     }
                 }
+            }
+        }
 
-                Some(field.clone())
-            })
-            .collect::<Vec<_>>();
+        let mut fields = vec![];
+        let data = if is_enum {
+            let content;
+            syn::braced!(content in input);
+            let mut variants = vec![];
+            for variant in
+                content.parse_terminated::<syn::Variant, token::Comma>(syn::Variant::parse)?
+            {
+                let mut indices = vec![];
+                for (i, f) in variant.fields.iter().enumerate() {
+                    parse_field_attrs(&mut functions, &f.attrs, fields.len());
+                    indices.push(fields.len());
+                    fields.push(match &f.ident {
+                        Some(ident) => syn::Member::Named(ident.clone()),
+                        None => syn::Member::Unnamed(i.into()),
+                    });
+                }
+                variants.push(Variant {
+                    ident: variant.ident,
+                    fields: indices,
+                });
+            }
+            Data::Enum(variants)
+        } else {
+            let raw_fields = input.parse::<syn::FieldsNamed>()?;
+            for f in raw_fields.named.iter() {
+                parse_field_attrs(&mut functions, &f.attrs, fields.len());
+                fields.push(syn::Member::Named(
+                    f.ident.clone().expect("named fields have idents"),
+                ));
+            }
+            Data::Struct
+        };
 
         Ok(Self {
             abga,
             ident,
             functions,
             fields,
+            data,
+        })
+    }
+}
+
+impl Top {
+    /// The body of a generated function: `call` for every field which isn't skipped, matching on
+    /// the variant for enums.
+    fn calls(
+        &self,
+        fields_to_skip: &HashSet<usize>,
+        call: impl Fn(&str, proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+        reference: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        use quote::quote;
+        let visited = |indices: &[usize]| {
+            indices
+                .iter()
+                .filter(|i| !fields_to_skip.contains(i))
+                .map(|&i| &self.fields[i])
+                .collect::<Vec<_>>()
+        };
+        match &self.data {
+            Data::Struct => {
+                let calls = visited(&(0..self.fields.len()).collect::<Vec<_>>())
+                    .into_iter()
+                    .map(|f| call(&field_name(f), quote!(#reference self.#f)));
+                quote! {
+                    #(#calls)*
+                    None
+                }
+            }
+            Data::Enum(variants) if variants.is_empty() => quote!(match *self {}),
+            Data::Enum(variants) => {
+                let arms = variants.iter().map(|variant| {
+                    let fields = visited(&variant.fields);
+                    let bindings = fields.iter().map(|f| binding(f)).collect::<Vec<_>>();
+                    let calls = fields
+                        .iter()
+                        .zip(&bindings)
+                        .map(|(f, binding)| call(&field_name(f), quote!(#binding)));
+                    let ident = &variant.ident;
+                    quote! {
+                        Self::#ident { #(#fields: #bindings,)* .. } => {
+                            #(#calls)*
+                        }
+                    }
+                });
+                quote! {
+                    match self {
+                        #(#arms)*
+                    }
+                    None
+                }
+            }
+        }
+    }
+
+    /// `variant_name` for enums.
+    fn variant_name(&self) -> Option<proc_macro2::TokenStream> {
+        use quote::quote;
+        let Data::Enum(variants) = &self.data else {
+            return None;
+        };
+        let arms = variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let name = ident.to_string();
+            quote!(Self::#ident { .. } => #name,)
+        });
+        Some(quote! {
+            /// The name of the active variant.
+            pub fn variant_name(&self) -> &'static str {
+                match *self {
+                    #(#arms)*
+                }
+            }
         })
     }
 }
@@ -251,7 +392,7 @@ impl ToTokens for Top {
             abga,
             ident,
             functions,
-            fields,
+            ..
         } = self;
 
         let fns = functions.iter().map(|FunctionJob {
@@ -268,49 +409,38 @@ impl ToTokens for Top {
                     where #(#predicates),*
                 }]
             };
+            let call = |fs: &str, value| {
+                quote! {
+                    if let Some(x) = f(#fs, #value) {
+                        return Some(x);
+                    }
+                }
+            };
             if !is_mut {
-                let calls = fields.iter().filter_map(|f| {
-                    (!fields_to_skip.contains(&f)).then(|| {
-                        let fs = f.to_string();
-                        quote! {
-                            if let Some(x) = f(#fs, &self.#f) {
-                                return Some(x);
-                            }
-                        }
-                    })
-                });
+                let body = self.calls(fields_to_skip, call, quote!(&));
                 quote! {
                     pub fn #name<ZZ>(&self, mut f: impl FnMut(&str, &#ty) -> Option<ZZ>) -> Option<ZZ>
                         #(#pred)*
                     {
-                        #(#calls)*
-                        None
+                        #body
                     }
                 }
             } else {
-                let calls = fields.iter().map(|f| {
-                    (!fields_to_skip.contains(&f)).then(|| {
-                        let fs = f.to_string();
-                        quote! {
-                            if let Some(x) = f(#fs, &mut self.#f) {
-                                return Some(x);
-                            }
-                        }
-                    })
-                });
+                let body = self.calls(fields_to_skip, call, quote!(&mut));
                 quote! {
                     pub fn #name<ZZ>(&mut self, mut f: impl FnMut(&str, &mut #ty) -> Option<ZZ>) -> Option<ZZ>
                         #(#pred)*
                     {
-                        #(#calls)*
-                        None
+                        #body
                     }
                 }
             }
         });
+        let variant_name = self.variant_name();
 
         quote! {
             impl #abga #ident #abga {
+                #variant_name
                 #(#fns)*
             }
         }
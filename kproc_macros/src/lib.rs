@@ -6,6 +6,7 @@ use quote::{quote, ToTokens};
 use syn::{parse_macro_input, parse_quote, ItemFn};
 mod clearable;
 mod field_iter;
+mod soa;
 
 #[proc_macro_derive(FieldIter, attributes(field_iter))]
 /// ```rust
@@ -87,6 +88,20 @@ pub fn clearable_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStre
         .into()
 }
 
+#[proc_macro_derive(IsCleared, attributes(clearable))]
+pub fn is_cleared_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as clearable::Top)
+        .is_cleared_tokens()
+        .into()
+}
+
+#[proc_macro_derive(SoA, attributes(soa))]
+pub fn soa_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as soa::Top)
+        .to_token_stream()
+        .into()
+}
+
 fn has_attr(attrs: &[syn::Attribute], attr_name: &str) -> bool {
     attrs.iter().any(|a| {
         a.parse_meta()
@@ -176,7 +191,7 @@ pub fn explain(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // TODO wish I could use Span::def_site() but needs nightly
     let callback = Ident::new("callback", Span::call_site());
     let callback_arg: syn::FnArg = parse_quote! {
-        mut #callback: impl FnMut(&str, Option<&str>, &dyn std::fmt::Display)
+        mut #callback: impl FnMut(&str, Option<&str>, &dyn ::core::fmt::Display)
     };
 
     new_function.sig.inputs.push(callback_arg);
@@ -0,0 +1,382 @@
+//! Generates a struct-of-arrays companion for a struct with named fields. For `Particle` it is
+//! `ParticleSoA`, with a `Vec` column per field, and `ParticleSoARef<'soa>`, which holds
+//! references to the fields of one element. Columns and reference fields keep the visibility of
+//! the original fields.
+//!
+//! The companion has `new`, `with_capacity`, `len`, `is_empty`, `push`, `pop`, `swap_remove` and
+//! `get`, and derives `kmacros::Clearable` and `kmacros::IsCleared`, so clearing it clears every
+//! column and keeps their capacity. Columns can be used directly where they are visible, but must
+//! all keep the same length.
+//!
+//! Container attributes, in `#[soa(...)]`:
+//! - `name = Particles` renames the companion, the reference struct becomes `ParticlesRef`.
+//! - `derive(Debug, Clone)` adds derives to the companion.
+//! - `columns(debug_columns = "dyn std::fmt::Debug")` generates a method like those of
+//!   `FieldIter`, which calls back with the name and the `Vec` of each column. Names ending in
+//!   `_mut` pass `&mut`. Columns can be left out with `#[soa(skip(debug_columns))]` on the field.
+//! - `bound(debug_columns = "T: std::fmt::Debug")` adds where clauses to a generated method.
+//!
+//! ```rust
+//! # use kmacros::{Clearable, SoA};
+//! #[derive(Clone, Copy, Debug, PartialEq, SoA)]
+//! #[soa(derive(Debug), columns(debug_columns = "dyn std::fmt::Debug"))]
+//! struct Particle {
+//!     pos: [f32; 3],
+//!     life: f32,
+//! }
+//!
+//! let mut particles = ParticleSoA::new();
+//! particles.push(Particle { pos: [0.0; 3], life: 1.0 });
+//! particles.push(Particle { pos: [1.0; 3], life: 0.5 });
+//! assert_eq!(particles.life, [1.0, 0.5]);
+//! assert_eq!(*particles.get(1).unwrap().life, 0.5);
+//!
+//! particles.debug_columns(|name, column| {
+//!     println!("{name} = {column:?}");
+//!     None::<()>
+//! });
+//!
+//! assert_eq!(particles.swap_remove(0).life, 1.0);
+//! particles.clear();
+//! assert!(particles.is_empty());
+//! ```
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse::Parse, punctuated::Punctuated, token, Attribute, Visibility};
+
+/// A column iterator from `columns(name = "Type")`.
+struct Columns {
+    name: Ident,
+    ty: syn::Type,
+    predicates: Vec<syn::WherePredicate>,
+}
+
+struct Field {
+    vis: Visibility,
+    ident: Ident,
+    ty: syn::Type,
+    /// Column iterators this field is left out of.
+    skip: Vec<Ident>,
+}
+
+pub struct Top {
+    vis: Visibility,
+    ident: Ident,
+    generics: syn::Generics,
+    /// The companion struct, `{ident}SoA` unless renamed.
+    soa: Ident,
+    derives: Vec<syn::Path>,
+    columns: Vec<Columns>,
+    fields: Vec<Field>,
+}
+
+/// Parses the comma separated items of every `#[soa(...)]` in `attrs` with `item`.
+fn parse_soa_attrs(
+    attrs: &[Attribute],
+    mut item: impl FnMut(Ident, syn::parse::ParseStream) -> syn::Result<()>,
+) -> syn::Result<()> {
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("soa")) {
+        attr.parse_args_with(|input: syn::parse::ParseStream| {
+            while !input.is_empty() {
+                let key = input.parse::<Ident>()?;
+                item(key, input)?;
+                if input.is_empty() {
+                    break;
+                }
+                input.parse::<token::Comma>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+/// `name = "..."` pairs, as in `columns(...)` and `bound(...)`.
+fn parse_named_strs(input: syn::parse::ParseStream) -> syn::Result<Vec<(Ident, syn::LitStr)>> {
+    let content;
+    syn::parenthesized!(content in input);
+    let mut pairs = vec![];
+    while !content.is_empty() {
+        let name = content.parse::<Ident>()?;
+        content.parse::<token::Eq>()?;
+        pairs.push((name, content.parse::<syn::LitStr>()?));
+        if content.is_empty() {
+            break;
+        }
+        content.parse::<token::Comma>()?;
+    }
+    Ok(pairs)
+}
+
+fn find_columns<'a>(columns: &'a mut [Columns], name: &Ident) -> syn::Result<&'a mut Columns> {
+    columns
+        .iter_mut()
+        .find(|c| c.name == *name)
+        .ok_or_else(|| syn::Error::new_spanned(name, "not a generated column iterator"))
+}
+
+impl Parse for Top {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse::<Visibility>()?;
+
+        if !input.peek(token::Struct) {
+            return Err(input.error("Only structs are supported"));
+        }
+        input.parse::<token::Struct>()?;
+        let ident = input.parse::<Ident>()?;
+
+        let mut generics = input.parse::<syn::Generics>()?;
+        generics.where_clause = input.parse()?;
+
+        if !input.peek(token::Brace) {
+            return Err(input.error("Only structs with named fields are supported"));
+        }
+
+        let mut soa = format_ident!("{}SoA", ident);
+        let mut derives = vec![];
+        let mut columns: Vec<Columns> = vec![];
+        // Bounds can come before the `columns` they refer to.
+        let mut bounds = vec![];
+        parse_soa_attrs(&attrs, |key, input| {
+            match key.to_string().as_str() {
+                "name" => {
+                    input.parse::<token::Eq>()?;
+                    soa = input.parse()?;
+                }
+                "derive" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    derives.extend(Punctuated::<syn::Path, token::Comma>::parse_terminated(
+                        &content,
+                    )?);
+                }
+                "columns" => {
+                    for (name, ty) in parse_named_strs(input)? {
+                        if columns.iter().any(|c| c.name == name) {
+                            return Err(syn::Error::new_spanned(name, "duplicate column iterator"));
+                        }
+                        columns.push(Columns {
+                            name,
+                            ty: ty.parse()?,
+                            predicates: vec![],
+                        });
+                    }
+                }
+                "bound" => bounds.extend(parse_named_strs(input)?),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        key,
+                        "unknown soa attribute, expected one of: name, derive, columns, bound",
+                    ))
+                }
+            }
+            Ok(())
+        })?;
+        for (name, predicates) in bounds {
+            let predicates = predicates
+                .parse_with(Punctuated::<syn::WherePredicate, token::Comma>::parse_terminated)?;
+            find_columns(&mut columns, &name)?
+                .predicates
+                .extend(predicates);
+        }
+
+        let raw_fields = input.parse::<syn::FieldsNamed>()?;
+        if raw_fields.named.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &ident,
+                "SoA needs at least one field to know its length",
+            ));
+        }
+        let mut fields = vec![];
+        for f in raw_fields.named {
+            let mut skip = vec![];
+            parse_soa_attrs(&f.attrs, |key, input| {
+                if key != "skip" {
+                    return Err(syn::Error::new_spanned(
+                        key,
+                        "unknown soa field attribute, expected `skip(...)`",
+                    ));
+                }
+                let content;
+                syn::parenthesized!(content in input);
+                for name in Punctuated::<Ident, token::Comma>::parse_terminated(&content)? {
+                    find_columns(&mut columns, &name)?;
+                    skip.push(name);
+                }
+                Ok(())
+            })?;
+            fields.push(Field {
+                vis: f.vis,
+                ident: f.ident.expect("named fields have idents"),
+                ty: f.ty,
+                skip,
+            });
+        }
+
+        Ok(Self {
+            vis,
+            ident,
+            generics,
+            soa,
+            derives,
+            columns,
+            fields,
+        })
+    }
+}
+
+impl Top {
+    /// The method for one `columns(...)` entry, in the style of `FieldIter`.
+    fn column_iter(&self, columns: &Columns) -> TokenStream {
+        let Columns {
+            name,
+            ty,
+            predicates,
+        } = columns;
+        let (receiver, reference) = if name.to_string().ends_with("_mut") {
+            (quote!(&mut self), quote!(&mut))
+        } else {
+            (quote!(&self), quote!(&))
+        };
+        let calls = self
+            .fields
+            .iter()
+            .filter(|f| !f.skip.contains(name))
+            .map(|f| {
+                let ident = &f.ident;
+                let name = ident.to_string();
+                quote! {
+                    if let Some(x) = f(#name, #reference self.#ident) {
+                        return Some(x);
+                    }
+                }
+            });
+        let pred = (!predicates.is_empty()).then(|| quote!(where #(#predicates),*));
+        quote! {
+            pub fn #name<ZZ>(#receiver, mut f: impl FnMut(&str, #reference #ty) -> Option<ZZ>) -> Option<ZZ>
+                #pred
+            {
+                #(#calls)*
+                None
+            }
+        }
+    }
+}
+
+impl ToTokens for Top {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Top {
+            vis,
+            ident,
+            generics,
+            soa,
+            derives,
+            columns,
+            fields,
+        } = self;
+        let soa_ref = format_ident!("{}Ref", soa);
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let lifetime = syn::Lifetime::new("'soa", Span::call_site());
+        let mut ref_generics = generics.clone();
+        ref_generics.params.insert(
+            0,
+            syn::GenericParam::Lifetime(syn::LifetimeDef::new(lifetime.clone())),
+        );
+        let (ref_impl_generics, ref_ty_generics, _) = ref_generics.split_for_impl();
+
+        let vises = fields.iter().map(|f| &f.vis).collect::<Vec<_>>();
+        let idents = fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+        let tys = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+        let first = idents[0];
+        let soa_doc = format!(
+            "Struct of arrays for [`{}`], with a column per field.",
+            ident
+        );
+        let ref_doc = format!("References to the fields of one element of [`{}`].", soa);
+        let column_iters = columns.iter().map(|c| self.column_iter(c));
+
+        quote! {
+            #[doc = #soa_doc]
+            #[derive(::kmacros::Clearable, ::kmacros::IsCleared, #(#derives),*)]
+            #vis struct #soa #generics #where_clause {
+                #(#vises #idents: ::kmacros::__private::Vec<#tys>,)*
+            }
+
+            #[doc = #ref_doc]
+            #vis struct #soa_ref #ref_generics #where_clause {
+                #(#vises #idents: &#lifetime #tys,)*
+            }
+
+            impl #ref_impl_generics Clone for #soa_ref #ref_ty_generics #where_clause {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl #ref_impl_generics Copy for #soa_ref #ref_ty_generics #where_clause {}
+
+            impl #impl_generics Default for #soa #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl #impl_generics #soa #ty_generics #where_clause {
+                pub fn new() -> Self {
+                    Self {
+                        #(#idents: ::kmacros::__private::Vec::new(),)*
+                    }
+                }
+
+                pub fn with_capacity(capacity: usize) -> Self {
+                    Self {
+                        #(#idents: ::kmacros::__private::Vec::with_capacity(capacity),)*
+                    }
+                }
+
+                pub fn len(&self) -> usize {
+                    self.#first.len()
+                }
+
+                pub fn is_empty(&self) -> bool {
+                    self.#first.is_empty()
+                }
+
+                pub fn push(&mut self, value: #ident #ty_generics) {
+                    #(self.#idents.push(value.#idents);)*
+                }
+
+                pub fn pop(&mut self) -> Option<#ident #ty_generics> {
+                    if self.is_empty() {
+                        return None;
+                    }
+                    Some(#ident {
+                        #(#idents: self.#idents.pop().expect("SoA columns have different lengths"),)*
+                    })
+                }
+
+                /// Removes the element at `index`, replacing it with the last one. Panics if
+                /// `index` is out of bounds.
+                pub fn swap_remove(&mut self, index: usize) -> #ident #ty_generics {
+                    #ident {
+                        #(#idents: self.#idents.swap_remove(index),)*
+                    }
+                }
+
+                pub fn get<#lifetime>(&#lifetime self, index: usize) -> Option<#soa_ref #ref_ty_generics> {
+                    if index >= self.len() {
+                        return None;
+                    }
+                    Some(#soa_ref {
+                        #(#idents: &self.#idents[index],)*
+                    })
+                }
+
+                #(#column_iters)*
+            }
+        }
+        .to_tokens(tokens);
+    }
+}
@@ -0,0 +1,89 @@
+use kmacros::Clearable;
+
+#[derive(Debug, PartialEq, Clearable)]
+enum State {
+    Running {
+        pending: Vec<u32>,
+    },
+    #[clearable(reset)]
+    Idle {
+        buffer: Vec<u8>,
+        #[clearable(default)]
+        count: u32,
+        #[clearable(skip)]
+        id: u32,
+        #[clearable(expr = "{} += 1")]
+        resets: u32,
+    },
+    Done(u32),
+}
+
+#[derive(Debug, PartialEq, Clearable)]
+enum Mode {
+    #[clearable(reset)]
+    Off,
+    On(Vec<u8>),
+}
+
+#[derive(Debug, PartialEq, Clearable)]
+enum Slot {
+    #[clearable(reset)]
+    Empty(Vec<u8>, #[clearable(default)] u32),
+    Full,
+}
+
+fn main() {
+    let mut state = State::Idle {
+        buffer: Vec::with_capacity(64),
+        count: 3,
+        id: 7,
+        resets: 0,
+    };
+    if let State::Idle { buffer, .. } = &mut state {
+        buffer.extend([1, 2, 3]);
+    }
+    state.clear();
+    match &state {
+        State::Idle {
+            buffer,
+            count,
+            id,
+            resets,
+        } => {
+            assert!(buffer.is_empty());
+            assert!(buffer.capacity() >= 64);
+            assert_eq!(*count, 0);
+            assert_eq!(*id, 7);
+            assert_eq!(*resets, 1);
+        }
+        _ => unreachable!(),
+    }
+
+    let mut state = State::Running {
+        pending: vec![1, 2],
+    };
+    state.clear();
+    assert_eq!(
+        state,
+        State::Idle {
+            buffer: vec![],
+            count: 0,
+            id: 0,
+            resets: 1,
+        }
+    );
+    let mut state = State::Done(3);
+    state.clear();
+    assert!(matches!(state, State::Idle { .. }));
+
+    let mut mode = Mode::On(vec![1]);
+    mode.clear();
+    assert_eq!(mode, Mode::Off);
+
+    let mut slot = Slot::Empty(vec![1], 3);
+    slot.clear();
+    assert_eq!(slot, Slot::Empty(vec![], 0));
+    let mut slot = Slot::Full;
+    slot.clear();
+    assert_eq!(slot, Slot::Empty(vec![], 0));
+}
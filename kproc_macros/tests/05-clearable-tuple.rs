@@ -0,0 +1,34 @@
+use kmacros::Clearable;
+
+#[derive(Clearable)]
+struct Scratch(Vec<u8>);
+
+#[derive(Clearable)]
+struct Pair<T>(
+    Vec<T>,
+    #[clearable(skip)] u32,
+    #[clearable(default)] u32,
+    #[clearable(expr = "{} = 3")] u32,
+)
+where
+    T: Clone;
+
+#[derive(Clearable)]
+struct Marker;
+
+fn main() {
+    let mut scratch = Scratch(Vec::with_capacity(16));
+    scratch.0.extend([1, 2, 3]);
+    scratch.clear();
+    assert!(scratch.0.is_empty());
+    assert!(scratch.0.capacity() >= 16);
+
+    let mut pair = Pair(vec![1, 2], 1, 2, 0);
+    pair.clear();
+    assert!(pair.0.is_empty());
+    assert_eq!(pair.1, 1);
+    assert_eq!(pair.2, 0);
+    assert_eq!(pair.3, 3);
+
+    Marker.cleared();
+}
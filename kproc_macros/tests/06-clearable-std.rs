@@ -0,0 +1,47 @@
+use kmacros::Clearable;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::ffi::OsString;
+use std::hash::BuildHasherDefault;
+use std::path::PathBuf;
+
+type Hasher = BuildHasherDefault<DefaultHasher>;
+
+#[derive(Default, Clearable)]
+struct Collections {
+    string: String,
+    deque: VecDeque<u32>,
+    btree_map: BTreeMap<u32, u32>,
+    btree_set: BTreeSet<u32>,
+    heap: BinaryHeap<u32>,
+    list: LinkedList<u32>,
+    path: PathBuf,
+    os_string: OsString,
+    map: HashMap<u32, u32, Hasher>,
+    set: HashSet<u32, Hasher>,
+}
+
+fn main() {
+    let mut c = Collections::default();
+    c.string.push_str("abc");
+    c.deque.push_back(1);
+    c.btree_map.insert(1, 2);
+    c.btree_set.insert(1);
+    c.heap.push(1);
+    c.list.push_back(1);
+    c.path.push("a/b");
+    c.os_string.push("abc");
+    c.map.insert(1, 2);
+    c.set.insert(1);
+    c.clear();
+    assert!(c.string.is_empty());
+    assert!(c.deque.is_empty());
+    assert!(c.btree_map.is_empty());
+    assert!(c.btree_set.is_empty());
+    assert!(c.heap.is_empty());
+    assert!(c.list.is_empty());
+    assert_eq!(c.path, PathBuf::new());
+    assert!(c.os_string.is_empty());
+    assert!(c.map.is_empty());
+    assert!(c.set.is_empty());
+}
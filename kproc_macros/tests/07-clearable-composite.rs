@@ -0,0 +1,112 @@
+use kmacros::{ClearReport, Clearable, IsCleared};
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+#[derive(Default, Clearable)]
+struct Composite {
+    array: [Vec<u8>; 2],
+    tuple: (Vec<u8>, String),
+    boxed: Box<Vec<u8>>,
+    shared: Rc<RefCell<Vec<u8>>>,
+    locked: Arc<Mutex<Vec<u8>>>,
+    rw: Arc<RwLock<String>>,
+    cell: Cell<u32>,
+    counter: AtomicUsize,
+    flag: Arc<AtomicBool>,
+}
+
+#[derive(Default, Clearable, IsCleared)]
+#[clearable(by_name, assert_cleared)]
+struct Inner {
+    #[clearable(group = "frame")]
+    frame: Vec<u8>,
+    kept: Vec<u8>,
+}
+
+fn inner() -> Inner {
+    Inner {
+        frame: vec![1, 2],
+        kept: vec![3],
+    }
+}
+
+/// Shared wrappers forward every `Clearable` method to the value behind them.
+fn check_forwarding<T: Clearable>(mut value: T, fill: impl Fn(&T), lens: impl Fn(&T) -> (usize, usize)) {
+    fill(&value);
+    assert!(value.retained_capacity() >= 2);
+    value.clear_group("frame");
+    assert_eq!(lens(&value), (0, 1));
+
+    fill(&value);
+    assert!(value.clear_field("kept"));
+    assert!(!value.clear_field("missing"));
+    assert_eq!(lens(&value), (2, 0));
+
+    fill(&value);
+    let mut report = ClearReport::new();
+    value.clear_with_report(&mut report);
+    let paths: Vec<_> = report.entries().iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, ["frame", "kept"]);
+    assert_eq!(lens(&value), (0, 0));
+
+    fill(&value);
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let checked = panic::catch_unwind(AssertUnwindSafe(|| value.debug_assert_cleared()));
+    panic::set_hook(hook);
+    assert_eq!(checked.is_err(), cfg!(debug_assertions));
+    value.clear_and_shrink_to(0);
+    value.debug_assert_cleared();
+    assert_eq!(value.retained_capacity(), 0);
+}
+
+fn main() {
+    let mut c = Composite::default();
+    c.array[1].push(1);
+    c.tuple.0.push(1);
+    c.tuple.1.push('a');
+    c.boxed.push(1);
+    c.shared.borrow_mut().push(1);
+    c.locked.lock().unwrap().push(1);
+    c.rw.write().unwrap().push('a');
+    c.cell.set(3);
+    c.counter.store(3, Ordering::SeqCst);
+    c.flag.store(true, Ordering::SeqCst);
+
+    let shared = c.shared.clone();
+    let locked = c.locked.clone();
+    c.clear();
+
+    assert!(c.array.iter().all(Vec::is_empty));
+    assert!(c.tuple.0.is_empty());
+    assert!(c.tuple.1.is_empty());
+    assert!(c.boxed.is_empty());
+    assert!(shared.borrow().is_empty());
+    assert!(locked.lock().unwrap().is_empty());
+    assert!(c.rw.read().unwrap().is_empty());
+    assert_eq!(c.cell.get(), 0);
+    assert_eq!(c.counter.load(Ordering::SeqCst), 0);
+    assert!(!c.flag.load(Ordering::SeqCst));
+
+    check_forwarding(
+        Rc::new(RefCell::new(inner())),
+        |v| *v.borrow_mut() = inner(),
+        |v| (v.borrow().frame.len(), v.borrow().kept.len()),
+    );
+    check_forwarding(
+        Arc::new(Mutex::new(inner())),
+        |v| *v.lock().unwrap() = inner(),
+        |v| {
+            let v = v.lock().unwrap();
+            (v.frame.len(), v.kept.len())
+        },
+    );
+    check_forwarding(
+        Arc::new(RwLock::new(inner())),
+        |v| *v.write().unwrap() = inner(),
+        |v| (v.read().unwrap().frame.len(), v.read().unwrap().kept.len()),
+    );
+}
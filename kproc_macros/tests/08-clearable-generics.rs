@@ -0,0 +1,82 @@
+use kmacros::Clearable;
+use std::fmt::Debug;
+
+#[derive(Clearable)]
+struct Inline<'a, T: Clone + Debug, const N: usize>
+where
+    T: PartialEq,
+{
+    items: Vec<T>,
+    #[clearable(skip)]
+    name: &'a str,
+    chunks: [Vec<T>; N],
+}
+
+#[derive(Clearable)]
+struct Defaults<T = u32, U = Vec<u8>> {
+    #[clearable(default)]
+    value: T,
+    inner: U,
+}
+
+// Only cleared fields get a bound, so `T` here doesn't need to be `Clearable`.
+#[derive(Clearable)]
+struct Skipped<T> {
+    #[clearable(skip)]
+    value: T,
+    items: Vec<u32>,
+}
+
+#[derive(Clearable)]
+#[clearable(bound = "T: Clearable + Clone")]
+struct Overridden<T>(T);
+
+#[derive(Clearable)]
+#[clearable(bound = "")]
+struct Unbounded<T>(#[clearable(skip)] T, Vec<u8>);
+
+#[derive(Debug, PartialEq, Clearable)]
+enum Generic<T> {
+    #[clearable(reset)]
+    Empty(Vec<T>),
+    Full(T),
+}
+
+struct NotClearable;
+
+fn main() {
+    let mut inline = Inline::<u32, 2> {
+        items: vec![1],
+        name: "inline",
+        chunks: [vec![1], vec![2]],
+    };
+    inline.clear();
+    assert!(inline.items.is_empty());
+    assert_eq!(inline.name, "inline");
+    assert!(inline.chunks.iter().all(Vec::is_empty));
+
+    let mut defaults: Defaults = Defaults {
+        value: 3,
+        inner: vec![1],
+    };
+    defaults.clear();
+    assert_eq!(defaults.value, 0);
+    assert!(defaults.inner.is_empty());
+
+    let mut skipped = Skipped {
+        value: NotClearable,
+        items: vec![1],
+    };
+    skipped.clear();
+    assert!(skipped.items.is_empty());
+
+    let mut overridden = Overridden(vec![1]);
+    overridden.clear();
+    assert!(overridden.0.is_empty());
+
+    Unbounded(NotClearable, vec![1]).clear();
+
+    let mut generic = Generic::Full(3);
+    generic.clear();
+    assert_eq!(generic, Generic::Empty(vec![]));
+}
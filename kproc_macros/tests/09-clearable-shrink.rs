@@ -0,0 +1,90 @@
+use kmacros::{Clearable, HighWatermark};
+
+#[derive(Default, Clearable)]
+struct Inner {
+    items: Vec<u32>,
+    #[clearable(skip)]
+    kept: Vec<u32>,
+}
+
+#[derive(Default, Clearable)]
+#[clearable(shrink_to = 64)]
+struct Request {
+    body: Vec<u8>,
+    #[clearable(shrink_to = 8)]
+    headers: Vec<u8>,
+    inner: Inner,
+}
+
+// Fields named like the parameters of the generated methods.
+#[derive(Clearable)]
+enum Budget {
+    #[clearable(reset)]
+    Open {
+        limit: Vec<u8>,
+        group: Vec<u8>,
+        report: Vec<u8>,
+    },
+    Closed,
+}
+
+#[derive(Clearable)]
+enum Slot {
+    #[clearable(reset)]
+    Empty(Vec<u8>),
+    Full,
+}
+
+fn main() {
+    let mut request = Request::default();
+    request.body.reserve(1024);
+    request.headers.reserve(1024);
+    request.inner.items.reserve(1024);
+    request.clear();
+    assert!(request.body.capacity() <= 64);
+    assert!(request.headers.capacity() <= 8);
+    assert!(request.inner.items.capacity() <= 64);
+
+    request.body.reserve(1024);
+    request.inner.items.reserve(1024);
+    request.inner.kept.reserve(1024);
+    assert!(request.retained_capacity() >= 1024);
+    request.clear_and_shrink_to(4);
+    assert!(request.body.capacity() <= 4);
+    assert!(request.inner.items.capacity() <= 4);
+    assert!(request.inner.kept.capacity() >= 1024);
+    assert!(request.retained_capacity() <= 4);
+
+    let mut slot = Slot::Empty(Vec::with_capacity(256));
+    assert!(slot.retained_capacity() >= 256);
+    slot.clear_and_shrink_to(0);
+    assert_eq!(slot.retained_capacity(), 0);
+    assert_eq!(Slot::Full.retained_capacity(), 0);
+
+    let mut budget = Budget::Open {
+        limit: Vec::with_capacity(256),
+        group: vec![1],
+        report: vec![2],
+    };
+    budget.clear_and_shrink_to(0);
+    assert!(matches!(
+        &budget,
+        Budget::Open { limit, group, report }
+            if limit.capacity() == 0 && group.is_empty() && report.is_empty()
+    ));
+    let mut budget = Budget::Closed;
+    budget.clear();
+    assert!(matches!(budget, Budget::Open { .. }));
+
+    // Recursively shrinks the derived struct once it stayed oversized for 3 clears.
+    let mut pooled = HighWatermark::new(Inner::default(), 16, 3);
+    for _ in 0..2 {
+        pooled.items.reserve(1024);
+        pooled.clear();
+        assert!(pooled.items.capacity() >= 1024);
+    }
+    pooled.clear();
+    assert!(pooled.items.capacity() <= 16);
+    pooled.clear();
+    assert!(pooled.items.capacity() <= 16);
+}
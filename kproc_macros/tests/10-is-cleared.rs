@@ -0,0 +1,109 @@
+use kmacros::{Clearable, IsCleared};
+use std::collections::HashMap;
+
+#[derive(Default, Clearable, IsCleared)]
+#[clearable(assert_cleared)]
+struct Frame {
+    items: Vec<u32>,
+    names: HashMap<String, u32>,
+    #[clearable(default)]
+    count: u32,
+    #[clearable(skip)]
+    id: u32,
+    #[clearable(expr = "{} += 1")]
+    generation: u32,
+}
+
+#[derive(Clearable, IsCleared)]
+enum State<T> {
+    #[clearable(reset)]
+    Idle(Vec<T>),
+    Running(T),
+}
+
+#[derive(Default, Clearable, IsCleared)]
+#[clearable(assert_cleared)]
+struct Broken {
+    #[clearable(expr = "{}.truncate(1)")]
+    items: Vec<u32>,
+    #[clearable(raw_expr = "self.extra.truncate(1)")]
+    extra: Vec<u32>,
+}
+
+// `bound` only replaces the bounds of `Clearable`, `IsCleared` keeps its inferred ones.
+#[derive(Clearable, IsCleared)]
+#[clearable(bound = "T: Clearable")]
+struct Bounded<T> {
+    items: Vec<T>,
+    x: T,
+}
+
+#[derive(Clearable, IsCleared)]
+#[clearable(bound(clearable = "T: Clearable", is_cleared = "T: IsCleared + Clone"))]
+struct BothBounded<T> {
+    x: T,
+}
+
+// A `clear` which doesn't fully reset, caught by `assert_cleared` in debug builds.
+impl IsCleared for Wrapper {
+    fn is_cleared(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[derive(Clearable)]
+#[clearable(assert_cleared)]
+struct Wrapper(#[clearable(expr = "{}.truncate(1)")] Vec<u32>);
+
+fn main() {
+    let mut frame = Frame {
+        items: vec![1],
+        count: 2,
+        id: 3,
+        ..Default::default()
+    };
+    frame.names.insert("a".into(), 1);
+    assert!(!frame.is_cleared());
+    frame.cleared();
+    assert!(frame.is_cleared());
+    assert_eq!(frame.id, 3);
+    assert_eq!(frame.generation, 1);
+
+    let mut state = State::Running(1);
+    assert!(!state.is_cleared());
+    state.clear();
+    assert!(state.is_cleared());
+    if let State::Idle(items) = &mut state {
+        items.push(1);
+    }
+    assert!(!state.is_cleared());
+
+    // Expression fields aren't checked.
+    let mut broken = Broken {
+        items: vec![1, 2],
+        extra: vec![1, 2],
+    };
+    broken.cleared();
+    assert!(broken.is_cleared());
+
+    let mut bounded = Bounded {
+        items: vec![vec![1]],
+        x: vec![2],
+    };
+    assert!(!bounded.is_cleared());
+    bounded.clear();
+    assert!(bounded.is_cleared());
+    let mut both = BothBounded { x: vec![1] };
+    both.clear();
+    assert!(both.is_cleared());
+
+    let pool = kmacros::pool::Pool::<Frame>::new();
+    pool.get().items.push(1);
+    assert!(pool.get().is_cleared());
+
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = std::panic::catch_unwind(|| {
+        Wrapper(vec![1, 2]).cleared();
+    });
+    assert_eq!(result.is_err(), cfg!(debug_assertions));
+}
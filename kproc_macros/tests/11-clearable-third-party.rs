@@ -0,0 +1,42 @@
+use arrayvec::{ArrayString, ArrayVec};
+use bytes::{BufMut, BytesMut};
+use indexmap::{IndexMap, IndexSet};
+use kmacros::{Clearable, IsCleared};
+use smallvec::SmallVec;
+use std::collections::hash_map::RandomState;
+
+#[derive(Default, Clearable, IsCleared)]
+struct Containers {
+    map: hashbrown::HashMap<u32, u32, RandomState>,
+    set: hashbrown::HashSet<u32, RandomState>,
+    index_map: IndexMap<u32, u32, RandomState>,
+    index_set: IndexSet<u32, RandomState>,
+    small: SmallVec<[u8; 4]>,
+    array: ArrayVec<u8, 4>,
+    string: ArrayString<8>,
+    bytes: BytesMut,
+}
+
+fn main() {
+    let mut c = Containers::default();
+    c.map.insert(1, 2);
+    c.set.insert(1);
+    c.index_map.insert(1, 2);
+    c.index_set.insert(1);
+    c.small.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+    c.array.push(1);
+    c.string.push_str("abc");
+    c.bytes.put_slice(b"hello");
+    assert!(!c.is_cleared());
+    assert!(c.retained_capacity() >= 6);
+
+    c.clear();
+    assert!(c.is_cleared());
+    assert!(c.small.spilled());
+
+    c.clear_and_shrink_to(0);
+    assert!(c.is_cleared());
+    assert!(!c.small.spilled());
+    assert_eq!(c.bytes.capacity(), 0);
+    assert_eq!(c.retained_capacity(), 0);
+}
@@ -0,0 +1,111 @@
+use kmacros::Clearable;
+
+#[derive(Default, Clearable)]
+struct Level {
+    #[clearable(group = "frame")]
+    particles: Vec<u32>,
+    #[clearable(group = "level")]
+    enemies: Vec<u32>,
+    #[clearable(group = "frame", group = "level", default)]
+    ticks: u32,
+    #[clearable(default)]
+    score: u32,
+}
+
+#[derive(Default, Clearable)]
+struct World {
+    #[clearable(group = "frame")]
+    events: Vec<String>,
+    levels: Vec<Level>,
+    current: Level,
+    boxed: Box<Level>,
+    #[clearable(skip)]
+    untouched: Level,
+}
+
+#[derive(Clearable)]
+struct Frame<T>(#[clearable(group = "frame")] Vec<T>, Vec<T>);
+
+#[derive(Clearable)]
+enum Slot {
+    #[clearable(reset)]
+    Empty {
+        #[clearable(group = "frame")]
+        scratch: Vec<u8>,
+        kept: Vec<u8>,
+        // Named like the `clear_group` parameter.
+        #[clearable(group = "frame")]
+        group: Vec<u8>,
+    },
+    Full(u32),
+}
+
+fn level() -> Level {
+    Level {
+        particles: vec![1],
+        enemies: vec![2],
+        ticks: 3,
+        score: 4,
+    }
+}
+
+fn main() {
+    let mut world = World {
+        events: vec!["spawn".into()],
+        levels: vec![level()],
+        current: level(),
+        boxed: Box::new(level()),
+        untouched: level(),
+    };
+
+    world.clear_frame();
+    assert!(world.events.is_empty());
+    for level in [&world.current, &*world.boxed] {
+        assert!(level.particles.is_empty());
+        assert_eq!(level.enemies, [2]);
+        assert_eq!(level.ticks, 0);
+        assert_eq!(level.score, 4);
+    }
+    // Containers without group support are left alone.
+    assert_eq!(world.levels[0].particles, [1]);
+    assert_eq!(world.untouched.particles, [1]);
+
+    world.current.ticks = 5;
+    world.clear_group("level");
+    assert!(world.current.enemies.is_empty());
+    assert_eq!(world.current.ticks, 0);
+    assert_eq!(world.current.score, 4);
+
+    // Unknown groups only propagate and clear nothing.
+    world.current.particles.push(1);
+    world.clear_group("unknown");
+    assert_eq!(world.current.particles, [1]);
+
+    world.clear();
+    assert!(world.levels.is_empty());
+    assert_eq!(world.current.score, 0);
+
+    let mut frame = Frame(vec![1], vec![2]);
+    frame.clear_frame();
+    assert!(frame.0.is_empty());
+    assert_eq!(frame.1, [2]);
+
+    let mut slot = Slot::Empty {
+        scratch: vec![1],
+        kept: vec![2],
+        group: vec![3],
+    };
+    slot.clear_frame();
+    assert!(matches!(
+        &slot,
+        Slot::Empty { scratch, kept, group } if scratch.is_empty() && kept == &[2] && group.is_empty()
+    ));
+    if let Slot::Empty { group, .. } = &mut slot {
+        group.push(3);
+    }
+    slot.clear_group("frame");
+    assert!(matches!(&slot, Slot::Empty { group, kept, .. } if group.is_empty() && kept == &[2]));
+    let mut slot = Slot::Full(1);
+    slot.clear_frame();
+    assert!(matches!(slot, Slot::Full(1)));
+}
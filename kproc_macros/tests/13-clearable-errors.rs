@@ -0,0 +1,124 @@
+use kmacros::Clearable;
+
+#[derive(Clearable)]
+struct Typo {
+    #[clearable(skp)]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct Unknown {
+    #[clearable(reset)]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct Conflict {
+    #[clearable(skip, default)]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct Duplicate {
+    #[clearable(shrink_to = 4)]
+    #[clearable(shrink_to = 8)]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct IgnoredShrink {
+    #[clearable(default, shrink_to = 4)]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct WrongValue {
+    #[clearable(shrink_to = "4")]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct MissingValue {
+    #[clearable(expr)]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct FlagValue {
+    #[clearable(skip = true)]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct BadExpr {
+    #[clearable(raw_expr = "self.a.clear(")]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct BadGroup {
+    #[clearable(group = "per frame")]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct NotAPath {
+    #[clearable(with = 42)]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+struct TwoHooks {
+    #[clearable(with = Vec::clear, value = vec![])]
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+#[clearable(assert_cleard)]
+struct ContainerTypo {
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+#[clearable]
+struct NoArgs {
+    a: Vec<u8>,
+}
+
+#[derive(Clearable)]
+enum Variant {
+    #[clearable(reset, reset)]
+    Empty,
+    Full(u32),
+}
+
+#[derive(Clearable)]
+#[clearable(by_name)]
+enum ByName {
+    #[clearable(reset)]
+    Empty,
+}
+
+#[derive(Clearable)]
+#[clearable(bound(clear = "T: Clearable"))]
+struct BoundTarget<T>(T);
+
+#[derive(Clearable)]
+enum OtherVariant {
+    #[clearable(reset)]
+    Idle,
+    Running {
+        #[clearable(skp)]
+        pending: Vec<u32>,
+        #[clearable(default, skip)]
+        x: u32,
+    },
+}
+
+#[derive(Clearable)]
+struct ReservedGroup {
+    #[clearable(group = "field")]
+    a: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,73 @@
+use kmacros::{Clearable, IsCleared};
+
+mod reset {
+    pub fn counter(counter: &mut u32) {
+        *counter = 1;
+    }
+
+    pub fn keep_first<T>(items: &mut Vec<T>) {
+        items.truncate(1);
+    }
+}
+
+const START: u32 = 10;
+
+#[derive(Debug, Clearable, IsCleared)]
+struct Foo<T> {
+    items: Vec<T>,
+    #[clearable(with = reset::counter)]
+    counter: u32,
+    #[clearable(with = reset::keep_first)]
+    history: Vec<T>,
+    #[clearable(with = String::clear)]
+    name: String,
+    #[clearable(value = format!("{}-{}", "id", START))]
+    id: String,
+    #[clearable(value = START + 1)]
+    next: u32,
+    #[clearable(value = vec![1, 2])]
+    scratch: Vec<u8>,
+}
+
+#[derive(Clearable)]
+enum State {
+    #[clearable(reset)]
+    Idle {
+        #[clearable(value = Some(START))]
+        timeout: Option<u32>,
+        #[clearable(with = reset::counter)]
+        restarts: u32,
+    },
+    Running,
+}
+
+fn main() {
+    let mut foo = Foo {
+        items: vec![1],
+        counter: 5,
+        history: vec![1, 2, 3],
+        name: "name".into(),
+        id: String::new(),
+        next: 0,
+        scratch: vec![],
+    };
+    foo.clear();
+    assert!(foo.items.is_empty());
+    assert_eq!(foo.counter, 1);
+    assert_eq!(foo.history, [1]);
+    assert!(foo.name.is_empty());
+    assert_eq!(foo.id, "id-10");
+    assert_eq!(foo.next, 11);
+    assert_eq!(foo.scratch, [1, 2]);
+    // Only `items` is checked.
+    assert!(foo.is_cleared());
+
+    let mut state = State::Running;
+    state.clear();
+    assert!(matches!(state, State::Idle { timeout: Some(10), restarts: 1 }));
+    if let State::Idle { restarts, .. } = &mut state {
+        *restarts = 3;
+    }
+    state.clear();
+    assert!(matches!(state, State::Idle { timeout: Some(10), restarts: 1 }));
+}
@@ -0,0 +1,83 @@
+use kmacros::Clearable;
+
+#[derive(Default, Clearable)]
+#[clearable(before = "Self::flush", after = "Self::reinit", shrink_to = 8)]
+struct Sink {
+    pending: Vec<u8>,
+    #[clearable(group = "frame")]
+    scratch: Vec<u8>,
+    #[clearable(skip)]
+    flushed: Vec<u8>,
+    #[clearable(skip)]
+    epoch: u64,
+}
+
+impl Sink {
+    fn flush(&mut self) {
+        self.flushed.append(&mut self.pending);
+    }
+
+    fn reinit(&mut self) {
+        assert!(self.pending.is_empty() && self.scratch.is_empty());
+        self.epoch += 1;
+    }
+}
+
+fn seed<T>(rng: &mut Rng<T>) {
+    rng.state = 42;
+}
+
+#[derive(Default, Clearable)]
+#[clearable(after = seed)]
+struct Rng<T> {
+    #[clearable(default)]
+    state: u64,
+    history: Vec<T>,
+}
+
+#[derive(Clearable)]
+#[clearable(after = State::count)]
+enum State {
+    #[clearable(reset)]
+    Idle(#[clearable(skip)] u32),
+    Busy,
+}
+
+impl State {
+    fn count(&mut self) {
+        if let State::Idle(resets) = self {
+            *resets += 1;
+        }
+    }
+}
+
+fn main() {
+    let mut sink = Sink::default();
+    sink.pending.extend_from_slice(b"abc");
+    sink.scratch.push(1);
+    sink.clear();
+    assert_eq!(sink.flushed, b"abc");
+    assert_eq!(sink.epoch, 1);
+
+    sink.pending.extend_from_slice(b"de");
+    sink.clear_and_shrink_to(0);
+    assert_eq!(sink.flushed, b"abcde");
+    assert_eq!(sink.epoch, 2);
+
+    // Groups leave the hooks alone.
+    sink.scratch.push(1);
+    sink.clear_frame();
+    assert!(sink.scratch.is_empty());
+    assert_eq!(sink.epoch, 2);
+
+    let mut rng = Rng { state: 7, history: vec!['a'] };
+    rng.clear();
+    assert_eq!(rng.state, 42);
+    assert!(rng.history.is_empty());
+
+    let mut state = State::Busy;
+    state.clear();
+    assert!(matches!(state, State::Idle(1)));
+    state.clear();
+    assert!(matches!(state, State::Idle(2)));
+}
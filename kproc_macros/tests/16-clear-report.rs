@@ -0,0 +1,161 @@
+use kmacros::{ClearReport, Clearable, HighWatermark};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+
+#[derive(Default, Clearable)]
+struct Inner {
+    items: Vec<u64>,
+    #[clearable(default)]
+    count: u32,
+}
+
+#[derive(Default, Clearable)]
+#[clearable(shrink_to = 64)]
+struct Request {
+    body: Vec<u8>,
+    #[clearable(shrink_to = 2)]
+    headers: Vec<(u32, u32)>,
+    inner: Inner,
+    r#type: String,
+    lookup: HashMap<u32, u32>,
+    #[clearable(skip)]
+    kept: Vec<u8>,
+}
+
+#[derive(Default, Clearable)]
+struct ThirdParty {
+    #[clearable(shrink_to = 4)]
+    map: hashbrown::HashMap<u32, u32, RandomState>,
+    small: smallvec::SmallVec<[u8; 4]>,
+    #[clearable(shrink_to = 8)]
+    bytes: bytes::BytesMut,
+}
+
+#[derive(Clearable)]
+enum Slot {
+    #[clearable(reset)]
+    Empty(Vec<u8>),
+    Full,
+}
+
+#[derive(Clearable)]
+enum Job {
+    #[clearable(reset)]
+    Idle {
+        // Named like the `clear_with_report` parameter.
+        report: Vec<u8>,
+    },
+    Running {
+        pending: Vec<u32>,
+        id: u32,
+    },
+    Done,
+}
+
+fn paths(report: &ClearReport) -> Vec<&str> {
+    report.entries().iter().map(|e| e.path.as_str()).collect()
+}
+
+fn main() {
+    let mut request = Request::default();
+    request.body.extend_from_slice(&[0; 1000]);
+    request.headers.extend([(1, 2), (3, 4), (5, 6), (7, 8)]);
+    request.inner.items.extend([1, 2, 3]);
+    request.inner.count = 3;
+    request.r#type.push_str("get");
+    request.lookup.insert(1, 2);
+    request.kept.push(1);
+
+    let mut report = ClearReport::new();
+    request.clear_with_report(&mut report);
+    assert_eq!(
+        paths(&report),
+        ["body", "headers", "inner.items", "type", "lookup"]
+    );
+    let entries = report.entries();
+    assert_eq!(entries[0].dropped, 1000);
+    assert!(entries[0].retained_bytes <= 64);
+    assert_eq!(entries[1].dropped, 4);
+    assert!(entries[1].retained_bytes <= 2 * 8);
+    assert_eq!(entries[2].dropped, 3);
+    assert!(entries[2].retained_bytes >= 3 * 8);
+    assert_eq!(report.dropped(), 1000 + 4 + 3 + 3 + 1);
+    assert_eq!(request.inner.count, 0);
+    assert_eq!(request.kept, [1]);
+
+    // Reports are reusable.
+    report.clear();
+    assert!(report.entries().is_empty());
+
+    let mut slot = Slot::Empty(vec![1, 2]);
+    slot.clear_with_report(&mut report);
+    assert_eq!(paths(&report), ["0"]);
+    assert_eq!(report.dropped(), 2);
+    report.clear();
+    let mut slot = Slot::Full;
+    slot.clear_with_report(&mut report);
+    assert_eq!(paths(&report), ["", "0"]);
+    assert_eq!(report.entries()[0].replaced, Some("Full"));
+    assert!(matches!(slot, Slot::Empty(_)));
+
+    // Switching variants records the replaced one under the enum's path, without counting the
+    // elements of its fields.
+    report.clear();
+    let mut job = Job::Running {
+        pending: (0..10_000).collect(),
+        id: 2,
+    };
+    report.enter("job");
+    job.clear_with_report(&mut report);
+    report.leave();
+    assert_eq!(paths(&report), ["job", "job.report"]);
+    assert_eq!(report.entries()[0].replaced, Some("Running"));
+    assert_eq!(report.entries()[0].dropped, 0);
+    assert_eq!(report.entries()[0].retained_bytes, 0);
+    assert_eq!(report.entries()[1].replaced, None);
+    assert_eq!(report.dropped(), 0);
+    assert!(matches!(&job, Job::Idle { report } if report.is_empty()));
+    report.clear();
+    let mut job = Job::Idle { report: vec![1, 2] };
+    job.clear_with_report(&mut report);
+    assert_eq!(paths(&report), ["report"]);
+    assert_eq!(report.dropped(), 2);
+    report.clear();
+    let mut job = Job::Done;
+    job.clear_with_report(&mut report);
+    assert_eq!(paths(&report), ["", "report"]);
+    assert_eq!(report.entries()[0].replaced, Some("Done"));
+    assert_eq!(report.dropped(), 0);
+
+    // Third-party containers report and shrink like the std ones.
+    report.clear();
+    let mut third = ThirdParty::default();
+    third.map.extend((0..1000).map(|i| (i, i)));
+    third.small.extend([1, 2, 3, 4, 5, 6]);
+    third.bytes.extend_from_slice(&[0; 100]);
+    third.clear_with_report(&mut report);
+    assert_eq!(paths(&report), ["map", "small", "bytes"]);
+    let entries = report.entries();
+    assert_eq!(entries[0].dropped, 1000);
+    assert!(third.map.capacity() <= 16);
+    assert!(entries[0].retained_bytes <= 16 * 8);
+    assert_eq!(entries[1].dropped, 6);
+    assert!(third.small.spilled());
+    assert_eq!(entries[2].dropped, 100);
+    assert!(third.bytes.capacity() <= 8);
+
+    // Values and nested collections.
+    report.clear();
+    let mut nested = [vec![1u8], vec![2, 3]];
+    nested.clear_with_report(&mut report);
+    assert_eq!(paths(&report), ["0", "1"]);
+
+    // The shrinking clear of a HighWatermark reports the shrunk capacity.
+    report.clear();
+    let mut buffer = HighWatermark::new(Vec::<u8>::with_capacity(1024), 16, 1);
+    buffer.push(1);
+    buffer.clear_with_report(&mut report);
+    assert_eq!(paths(&report), [""]);
+    assert_eq!(report.dropped(), 1);
+    assert!(report.retained_bytes() <= 16);
+}
@@ -0,0 +1,53 @@
+use kmacros::registry::ClearRegistry;
+use kmacros::Clearable;
+
+#[derive(Default, Clearable)]
+struct Frame {
+    #[clearable(group = "frame")]
+    events: Vec<u32>,
+    totals: Vec<u32>,
+}
+
+fn clear_all(values: &mut [&mut dyn Clearable]) {
+    for value in values {
+        value.clear();
+    }
+}
+
+fn main() {
+    let mut a = vec![1];
+    let mut b = String::from("b");
+    let mut frame = Frame {
+        events: vec![1],
+        totals: vec![2],
+    };
+    clear_all(&mut [&mut a, &mut b, &mut frame]);
+    assert!(a.is_empty() && b.is_empty() && frame.totals.is_empty());
+
+    let mut boxed: Vec<Box<dyn Clearable>> = vec![Box::new(vec![1u8]), Box::<Frame>::default()];
+    boxed.iter_mut().for_each(|x| x.clear());
+
+    let mut frame = Frame {
+        events: vec![1],
+        totals: vec![2],
+    };
+    let mut other = Frame {
+        events: vec![1],
+        totals: vec![2],
+    };
+    let mut registry = ClearRegistry::new();
+    registry
+        .register("frame", &mut frame)
+        .register("other", &mut other)
+        .register_boxed("owned", Box::new(vec![1u8]));
+    assert_eq!(registry.names().collect::<Vec<_>>(), ["frame", "other", "owned"]);
+    assert!(registry.set_enabled("other", false));
+    assert!(!registry.set_enabled("missing", false));
+    assert_eq!(registry.is_enabled("other"), Some(false));
+    assert_eq!(registry.is_enabled("missing"), None);
+    registry.clear_group("frame");
+    registry.clear();
+    drop(registry);
+    assert!(frame.events.is_empty() && frame.totals.is_empty());
+    assert_eq!((other.events.len(), other.totals.len()), (1, 1));
+}
@@ -0,0 +1,67 @@
+use kmacros::{Clearable, GenHandle, Generational};
+use std::collections::HashMap;
+
+#[derive(Default, Clearable)]
+struct Scene {
+    #[clearable(group = "frame")]
+    sprites: Generational<Vec<&'static str>>,
+    names: Generational<HashMap<String, u32>>,
+}
+
+#[derive(Default, Clearable)]
+#[clearable(by_name)]
+struct Level {
+    #[clearable(group = "frame")]
+    enemies: Vec<u32>,
+    score: Vec<u32>,
+}
+
+fn lookup(scene: &Scene, handle: GenHandle<String>) -> Option<u32> {
+    scene.names.get(&scene.names.resolve(handle)?).copied()
+}
+
+fn main() {
+    let mut scene = Scene::default();
+    let player = scene.sprites.push("player");
+    scene.names.value_mut().insert("player".into(), 1);
+    let name = scene.names.handle("player".to_string());
+    assert_eq!(lookup(&scene, name.clone()), Some(1));
+
+    if let Some(sprite) = scene.sprites.get_mut(player) {
+        *sprite = "hero";
+    }
+    assert_eq!(scene.sprites.get(player), Some(&"hero"));
+
+    // The group is forwarded to `names`, which invalidates its handles too.
+    scene.clear_frame();
+    assert_eq!(scene.sprites.get(player), None);
+    assert_eq!(scene.sprites.generation(), 1);
+    assert_eq!(scene.names.generation(), 1);
+    assert_eq!(lookup(&scene, name.clone()), None);
+
+    scene.clear();
+    assert_eq!(scene.names.generation(), 2);
+    assert!(!scene.names.is_current(&name));
+    assert_eq!(name.key(), "player");
+
+    // Handles are invalidated even when the same slot is reused.
+    let again = scene.sprites.push("again");
+    assert_eq!(again.key(), player.key());
+    assert_eq!(scene.sprites.get(player), None);
+    assert_eq!(scene.sprites.get(again), Some(&"again"));
+
+    // Group and field clears invalidate handles into the wrapped value.
+    let mut level = Generational::new(Level {
+        enemies: vec![1, 2],
+        score: vec![3],
+    });
+    let enemy = level.handle(1);
+    level.clear_group("frame");
+    assert!(level.enemies.is_empty());
+    assert_eq!(level.resolve(enemy), None);
+    let score = level.handle(0);
+    assert!(!level.clear_field("missing"));
+    assert_eq!(level.resolve(score), Some(0));
+    assert!(level.clear_field("score"));
+    assert_eq!(level.resolve(score), None);
+}
@@ -0,0 +1,67 @@
+use kmacros::epoch_vec::EpochVec;
+use kmacros::{Clearable, HighWatermark, IsCleared};
+
+#[derive(Clearable, IsCleared)]
+struct Search {
+    visited: EpochVec<bool>,
+    distance: EpochVec<u32, u8>,
+    queue: Vec<usize>,
+}
+
+fn bfs(search: &mut Search, edges: &[Vec<usize>], start: usize) {
+    search.clear();
+    search.visited[start] = true;
+    search.queue.push(start);
+    while let Some(node) = search.queue.pop() {
+        for &next in &edges[node] {
+            if !search.visited[next] {
+                search.visited[next] = true;
+                search.distance[next] = search.distance[node] + 1;
+                search.queue.insert(0, next);
+            }
+        }
+    }
+}
+
+fn main() {
+    let edges = vec![vec![1], vec![2], vec![0], vec![]];
+    let mut search = Search {
+        visited: EpochVec::new(edges.len()),
+        distance: EpochVec::new(edges.len()),
+        queue: vec![],
+    };
+    // Enough searches for the u8 epoch to wrap a few times.
+    for round in 0..1000 {
+        let start = round % 3;
+        bfs(&mut search, &edges, start);
+        assert_eq!(search.distance[start], 0);
+        assert_eq!(search.distance[(start + 2) % 3], 2);
+        assert!(!search.visited[3]);
+        assert!(!search.is_cleared());
+    }
+    search.clear();
+    assert!(search.is_cleared());
+    assert_eq!(search.visited.iter().filter(|v| **v).count(), 0);
+
+    let mut values = EpochVec::<String>::new(2);
+    values.set(0, "a".into());
+    assert!(values.is_written(0) && !values.is_written(1));
+    values.clear();
+    assert_eq!(values.get(0).map(String::as_str), Some(""));
+    assert_eq!(values.get(2), None);
+    values.get_mut(0).unwrap().push('b');
+    assert_eq!(values[0], "b");
+    values.resize(3);
+    assert_eq!(values.len(), 3);
+    assert_eq!(values[2], "");
+
+    // The slots aren't spare capacity, so they never count as oversized.
+    let mut watermark = HighWatermark::new(EpochVec::<u32>::new(100), 10, 1);
+    assert_eq!(watermark.retained_capacity(), 0);
+    watermark.clear();
+    watermark.clear_and_shrink_to(0);
+    assert_eq!(watermark.retained_capacity(), 0);
+    let values = watermark.into_inner();
+    assert_eq!(values.len(), 100);
+    assert!(values.is_cleared());
+}
@@ -0,0 +1,53 @@
+use kmacros::{Clearable, SecureClearable};
+
+#[derive(Clearable)]
+struct Session<K> {
+    #[clearable(zeroize)]
+    token: String,
+    #[clearable(zeroize)]
+    key: [u8; 4],
+    #[clearable(zeroize, group = "frame")]
+    scratch: Vec<u8>,
+    #[clearable(zeroize)]
+    generic: K,
+    name: String,
+}
+
+#[derive(Clearable)]
+enum Secret {
+    #[clearable(reset)]
+    Empty(#[clearable(zeroize)] Vec<u8>),
+    Other,
+}
+
+fn main() {
+    let mut session = Session {
+        token: "secret".into(),
+        key: [1, 2, 3, 4],
+        scratch: b"abc".to_vec(),
+        generic: vec![9u8; 8],
+        name: "name".into(),
+    };
+    let capacity = session.scratch.capacity();
+    session.clear_frame();
+    assert!(session.scratch.is_empty());
+    assert_eq!(session.scratch.capacity(), capacity);
+    // The spare capacity was wiped too.
+    assert!(session.scratch.spare_capacity_mut()[..3]
+        .iter()
+        .all(|b| unsafe { b.assume_init() } == 0));
+    assert_eq!(session.token, "secret");
+
+    session.clear();
+    assert!(session.token.is_empty() && session.name.is_empty());
+    assert_eq!(session.key, [0; 4]);
+    assert!(session.generic.is_empty());
+
+    let mut secret = Secret::Empty(vec![1, 2]);
+    secret.clear();
+    assert!(matches!(&secret, Secret::Empty(bytes) if bytes.is_empty()));
+
+    let mut bytes = [1u8, 2, 3];
+    bytes[..2].secure_clear();
+    assert_eq!(bytes, [0, 0, 3]);
+}
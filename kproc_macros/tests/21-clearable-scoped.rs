@@ -0,0 +1,60 @@
+use kmacros::{cleared_scope, ClearOnDrop, Clearable};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[derive(Default, Clearable)]
+struct Scratch {
+    lines: Vec<String>,
+    #[clearable(skip)]
+    runs: u32,
+}
+
+fn fill(scratch: &mut Scratch, fail: bool) -> Option<usize> {
+    let mut scratch = scratch.scoped();
+    scratch.runs += 1;
+    scratch.lines.push("line".into());
+    if fail {
+        return None;
+    }
+    Some(scratch.lines.len())
+}
+
+fn main() {
+    let mut scratch = Scratch::default();
+    assert_eq!(fill(&mut scratch, false), Some(1));
+    assert_eq!(fill(&mut scratch, true), None);
+    assert!(scratch.lines.is_empty());
+    assert_eq!(scratch.runs, 2);
+
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut scratch = scratch.scoped();
+        scratch.lines.push("partial".into());
+        panic!("failed halfway");
+    }));
+    let _ = std::panic::take_hook();
+    assert!(result.is_err());
+    assert!(scratch.lines.is_empty());
+
+    let mut buffer = Vec::new();
+    for i in 0..3 {
+        cleared_scope!(buffer);
+        buffer.push(i);
+        if i == 1 {
+            continue;
+        }
+        assert_eq!(*buffer, [i]);
+    }
+    assert!(buffer.is_empty());
+
+    let mut scratch = Scratch::default();
+    {
+        cleared_scope!(let lines = &mut scratch.lines);
+        lines.push("a".into());
+    }
+    assert!(scratch.lines.is_empty());
+
+    let mut text = String::from("text");
+    let value: &mut dyn Clearable = &mut text;
+    drop(ClearOnDrop::new(value));
+    assert!(text.is_empty());
+}
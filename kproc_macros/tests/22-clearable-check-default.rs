@@ -0,0 +1,77 @@
+use kmacros::Clearable;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[derive(Debug, Default, Clearable)]
+#[clearable(check_default)]
+struct Good<T> {
+    items: Vec<T>,
+    #[clearable(value = 0)]
+    total: u32,
+    #[clearable(skip)]
+    runs: u32,
+}
+
+#[derive(Debug, Clearable)]
+#[clearable(check_default)]
+struct Drifted {
+    items: Vec<u32>,
+    #[clearable(value = 1)]
+    limit: u32,
+}
+
+impl Default for Drifted {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            limit: 10,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clearable)]
+#[clearable(check_default)]
+enum State {
+    #[clearable(reset)]
+    Idle { queue: Vec<u8> },
+    Busy(u32),
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State::Idle { queue: vec![] }
+    }
+}
+
+#[derive(Debug, Default, Clearable)]
+#[clearable(check_default)]
+enum WrongDefault {
+    #[clearable(reset)]
+    Idle(Vec<u8>),
+    #[default]
+    Busy,
+}
+
+fn panics(f: impl FnOnce()) -> bool {
+    catch_unwind(AssertUnwindSafe(f)).is_err()
+}
+
+fn main() {
+    let mut good = Good {
+        items: vec!['a'],
+        total: 3,
+        runs: 7,
+    };
+    good.clear();
+    assert_eq!(good.runs, 7);
+
+    let mut state = State::Busy(1);
+    state.clear();
+    assert_eq!(state, State::default());
+
+    std::panic::set_hook(Box::new(|_| {}));
+    let drifted = panics(|| Drifted { items: vec![1], limit: 3 }.clear());
+    let wrong_default = panics(|| WrongDefault::Idle(vec![1]).clear());
+    let _ = std::panic::take_hook();
+    assert_eq!(drifted, cfg!(debug_assertions));
+    assert_eq!(wrong_default, cfg!(debug_assertions));
+}
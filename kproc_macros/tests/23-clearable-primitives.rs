@@ -0,0 +1,42 @@
+use kmacros::{Clearable, IsCleared};
+use std::marker::PhantomData;
+
+#[derive(Debug, Default, PartialEq, Clearable, IsCleared)]
+#[clearable(check_default)]
+struct Stats<T> {
+    samples: Vec<f64>,
+    count: u32,
+    total: i64,
+    mean: f64,
+    dirty: bool,
+    grade: char,
+    unit: (),
+    big: u128,
+    offset: isize,
+    pairs: [(u8, i16); 2],
+    marker: PhantomData<T>,
+}
+
+fn main() {
+    let mut stats = Stats::<String> {
+        samples: vec![1.0, 2.0],
+        count: 2,
+        total: -3,
+        mean: 1.5,
+        dirty: true,
+        grade: 'A',
+        unit: (),
+        big: 1,
+        offset: -1,
+        pairs: [(1, 2), (3, 4)],
+        marker: PhantomData,
+    };
+    assert!(!stats.is_cleared());
+    stats.clear();
+    assert!(stats.is_cleared());
+    assert_eq!(stats, Stats::default());
+
+    let mut counters = [1u32, 2, 3];
+    counters.clear();
+    assert_eq!(counters, [0; 3]);
+}
@@ -0,0 +1,118 @@
+use kmacros::registry::ClearRegistry;
+use kmacros::Clearable;
+use std::sync::Mutex;
+
+#[derive(Default, Clearable)]
+#[clearable(by_name)]
+struct Physics {
+    particles: Vec<u32>,
+    #[clearable(default)]
+    gravity: f32,
+    #[clearable(shrink_to = 2)]
+    contacts: Vec<u32>,
+}
+
+#[derive(Default, Clearable)]
+#[clearable(by_name)]
+struct State {
+    physics: Physics,
+    locked: Mutex<Physics>,
+    log: Vec<String>,
+    #[clearable(value = 1)]
+    frame: u64,
+    #[clearable(skip)]
+    seed: u64,
+    r#type: Vec<u8>,
+}
+
+#[derive(Clearable)]
+#[clearable(by_name)]
+struct Pair(Vec<u8>, #[clearable(skip)] Vec<u8>);
+
+#[derive(Default, Clearable)]
+struct Opaque {
+    items: Vec<u8>,
+}
+
+fn physics() -> Physics {
+    Physics {
+        particles: vec![1],
+        gravity: 9.8,
+        contacts: Vec::with_capacity(16),
+    }
+}
+
+fn main() {
+    assert_eq!(Physics::CLEARABLE_FIELDS, ["particles", "gravity", "contacts"]);
+    assert_eq!(
+        State::CLEARABLE_FIELDS,
+        ["physics", "locked", "log", "frame", "type"]
+    );
+    assert_eq!(Pair::CLEARABLE_FIELDS, ["0"]);
+
+    let mut state = State {
+        physics: physics(),
+        locked: Mutex::new(physics()),
+        log: vec!["start".into()],
+        frame: 7,
+        seed: 42,
+        r#type: vec![1],
+    };
+
+    assert!(state.clear_field("log"));
+    assert!(state.log.is_empty());
+    assert_eq!(state.physics.particles, [1]);
+
+    assert!(state.clear_field("frame"));
+    assert_eq!(state.frame, 1);
+    assert!(state.clear_field("type"));
+    assert!(state.r#type.is_empty());
+
+    assert!(state.clear_field("physics.gravity"));
+    assert_eq!(state.physics.gravity, 0.0);
+    assert_eq!(state.physics.particles, [1]);
+
+    // `shrink_to` still applies.
+    assert!(state.clear_field("physics.contacts"));
+    assert!(state.physics.contacts.capacity() <= 2);
+
+    assert!(state.clear_field("locked.particles"));
+    assert!(state.locked.get_mut().unwrap().particles.is_empty());
+
+    assert!(state.clear_field("physics"));
+    assert!(state.physics.particles.is_empty());
+
+    // Skipped, unknown and too deep paths clear nothing.
+    assert!(!state.clear_field("seed"));
+    assert_eq!(state.seed, 42);
+    assert!(!state.clear_field("missing"));
+    assert!(!state.clear_field("physics.missing"));
+    assert!(!state.clear_field("log.len"));
+    assert!(!state.clear_field(""));
+
+    // Types without `by_name` don't support it.
+    let mut opaque = Opaque { items: vec![1] };
+    assert!(!opaque.clear_field("items"));
+    assert_eq!(opaque.items, [1]);
+
+    let mut pair = Pair(vec![1], vec![2]);
+    assert!(pair.clear_field("0"));
+    assert!(!pair.clear_field("1"));
+    assert!(pair.0.is_empty());
+    assert_eq!(pair.1, [2]);
+
+    let mut state = State {
+        physics: physics(),
+        ..Default::default()
+    };
+    let mut events = vec![1];
+    let mut registry = ClearRegistry::new();
+    registry.register("world", &mut state);
+    registry.register("events", &mut events);
+    assert!(registry.clear_field("world.physics.particles"));
+    assert!(!registry.clear_field("world.physics.missing"));
+    assert!(registry.clear_field("events"));
+    drop(registry);
+    assert!(state.physics.particles.is_empty());
+    assert!(events.is_empty());
+}
@@ -0,0 +1,97 @@
+use kmacros::{ClearReport, Clearable, IsCleared, SoA};
+use std::any::Any;
+use std::fmt::Debug;
+
+#[derive(Clone, Copy, Debug, PartialEq, SoA)]
+#[soa(
+    name = ParticlesSoA,
+    derive(Debug, Clone),
+    columns(debug_columns = "dyn Debug", any_columns_mut = "dyn Any")
+)]
+pub struct Particle {
+    pub pos: [f32; 3],
+    pub vel: [f32; 3],
+    #[soa(skip(debug_columns))]
+    pub life: f32,
+}
+
+#[derive(Debug, PartialEq, SoA)]
+#[soa(columns(debug_columns = "dyn Debug"), bound(debug_columns = "T: Debug"))]
+struct Tagged<T: Clone, const N: usize> {
+    tag: String,
+    values: [T; N],
+}
+
+fn particle(i: u8) -> Particle {
+    let x = f32::from(i);
+    Particle {
+        pos: [x; 3],
+        vel: [-x; 3],
+        life: x / 10.0,
+    }
+}
+
+fn main() {
+    let mut particles = ParticlesSoA::with_capacity(8);
+    assert!(particles.is_empty());
+    for i in 0..4 {
+        particles.push(particle(i));
+    }
+    assert_eq!(particles.len(), 4);
+    assert_eq!(particles.life, [0.0, 0.1, 0.2, 0.3]);
+
+    let second = particles.get(1).unwrap();
+    let copy = second;
+    assert_eq!((*second.pos, *copy.life), ([1.0; 3], 0.1));
+    assert!(particles.get(4).is_none());
+
+    assert_eq!(particles.swap_remove(0), particle(0));
+    assert_eq!(particles.life, [0.3, 0.1, 0.2]);
+    assert_eq!(particles.pop(), Some(particle(2)));
+    assert_eq!(particles.len(), 2);
+
+    let mut names = vec![];
+    particles.debug_columns(|name, column| {
+        names.push(format!("{name} = {column:?}"));
+        None::<()>
+    });
+    assert_eq!(
+        names,
+        [
+            "pos = [[3.0, 3.0, 3.0], [1.0, 1.0, 1.0]]",
+            "vel = [[-3.0, -3.0, -3.0], [-1.0, -1.0, -1.0]]",
+        ]
+    );
+
+    let found = particles.any_columns_mut(|name, column| {
+        let life = column.downcast_mut::<Vec<f32>>()?;
+        life[0] = 1.0;
+        Some(name.to_string())
+    });
+    assert_eq!(found.as_deref(), Some("life"));
+    assert_eq!(particles.life, [1.0, 0.1]);
+
+    let backup = particles.clone();
+    let mut report = ClearReport::new();
+    particles.clear_with_report(&mut report);
+    assert!(particles.is_cleared());
+    assert_eq!(particles.pop(), None);
+    assert!(particles.life.capacity() >= 8);
+    assert_eq!(report.entries().len(), 3);
+    assert_eq!(report.entries()[2].path, "life");
+    assert_eq!(report.dropped(), 6);
+    assert_eq!(backup.len(), 2);
+
+    let mut tagged = TaggedSoA::<u8, 2>::default();
+    tagged.push(Tagged {
+        tag: "a".into(),
+        values: [1, 2],
+    });
+    assert_eq!(tagged.get(0).unwrap().tag, "a");
+    tagged.debug_columns(|name, column| {
+        assert_eq!(name == "tag", format!("{column:?}") == r#"["a"]"#);
+        None::<()>
+    });
+    tagged.clear_and_shrink_to(0);
+    assert_eq!(tagged.values.capacity(), 0);
+}
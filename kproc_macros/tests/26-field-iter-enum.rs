@@ -0,0 +1,72 @@
+use kproc_macros::FieldIter;
+use std::any::Any;
+use std::fmt::Debug;
+
+#[derive(Debug, FieldIter)]
+#[allow(dead_code)]
+#[field_iter(debug_iter = "dyn Debug", any_iter_mut = "dyn Any")]
+#[field_iter(bound(debug_iter = "T: Debug"))]
+#[field_iter(bound(any_iter_mut = "T: 'static"))]
+enum Event<T> {
+    Click {
+        x: i32,
+        y: i32,
+        #[field_iter(skip(debug_iter))]
+        button: u8,
+    },
+    Key(char, #[field_iter(skip(any_iter_mut))] T),
+    Resize {
+        // Same name as a field of another variant, only skipped here.
+        #[field_iter(skip(debug_iter))]
+        x: u32,
+        r#type: String,
+    },
+    Close,
+}
+
+#[derive(FieldIter)]
+#[field_iter(debug_iter = "dyn Debug")]
+enum Never {}
+
+fn fields<T: Debug>(event: &Event<T>) -> Vec<String> {
+    let mut fields = vec![];
+    event.debug_iter(|name, value| {
+        fields.push(format!("{}.{name} = {value:?}", event.variant_name()));
+        None::<()>
+    });
+    fields
+}
+
+fn main() {
+    let click = Event::<u8>::Click {
+        x: 1,
+        y: 2,
+        button: 0,
+    };
+    assert_eq!(click.variant_name(), "Click");
+    assert_eq!(fields(&click), ["Click.x = 1", "Click.y = 2"]);
+
+    let key = Event::Key('q', "ctrl");
+    assert_eq!(fields(&key), ["Key.0 = 'q'", r#"Key.1 = "ctrl""#]);
+
+    let resize = Event::<u8>::Resize {
+        x: 3,
+        r#type: "full".into(),
+    };
+    assert_eq!(fields(&resize), [r#"Resize.r#type = "full""#]);
+
+    let close = Event::<u8>::Close;
+    assert_eq!(close.variant_name(), "Close");
+    assert!(fields(&close).is_empty());
+
+    let mut key = Event::Key('a', 5u64);
+    let found = key.any_iter_mut(|name, value| {
+        let c = value.downcast_mut::<char>()?;
+        *c = c.to_ascii_uppercase();
+        Some(name.to_string())
+    });
+    assert_eq!(found.as_deref(), Some("0"));
+    assert!(matches!(key, Event::Key('A', 5)));
+
+    let _ = |never: Never| never.debug_iter(|_, _| None::<()>);
+}
@@ -0,0 +1,117 @@
+use kmacros::pool::{LocalPool, Pool, PoolStats, SyncPool};
+use kmacros::Clearable;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct Tracked(Vec<u8>);
+
+impl Clearable for Tracked {
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROPPED.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+thread_local! {
+    static BUFFERS: Pool<Vec<u8>> = Pool::new();
+}
+
+static LOCAL: LocalPool<Vec<u8>> = LocalPool::new(&BUFFERS);
+
+fn main() {
+    // Returned objects beyond `max_retained` are dropped.
+    let pool = Pool::<Tracked>::with_max_retained(2);
+    let items = (0..3).map(|_| pool.get()).collect::<Vec<_>>();
+    drop(items);
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        pool.stats(),
+        PoolStats {
+            hits: 0,
+            misses: 3,
+            size: 2
+        }
+    );
+
+    // Retained objects are cleared and reused with their capacity.
+    let mut item = pool.get();
+    item.0.extend_from_slice(&[1, 2, 3]);
+    let capacity = item.0.capacity();
+    drop(item);
+    let item = pool.get();
+    assert!(item.0.is_empty());
+    assert_eq!(item.0.capacity(), capacity);
+    drop(item);
+    assert_eq!(pool.stats().hits, 2);
+
+    // Lowering the limit drops the extra objects right away.
+    let dropped = DROPPED.load(Ordering::SeqCst);
+    pool.set_max_retained(1);
+    assert_eq!(pool.max_retained(), 1);
+    assert_eq!(pool.stats().size, 1);
+    assert_eq!(DROPPED.load(Ordering::SeqCst), dropped + 1);
+
+    // Detached objects don't go back.
+    let mut item = pool.get();
+    item.0.push(7);
+    let detached = item.detach();
+    assert_eq!(detached.0, [7]);
+    assert_eq!(pool.stats().size, 0);
+    drop(detached);
+    assert_eq!(pool.stats().size, 0);
+
+    // Clones are handles to the same pool.
+    pool.clone().get();
+    assert_eq!(pool.stats().size, 1);
+
+    let pool = SyncPool::<Vec<u8>>::with_max_retained(4);
+    std::thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| pool.get().push(1));
+        }
+    });
+    let stats = pool.stats();
+    assert_eq!(stats.hits + stats.misses, 8);
+    assert!(stats.size >= 1 && stats.size <= 4);
+    assert!(pool.get().is_empty());
+    pool.set_max_retained(0);
+    assert_eq!(pool.stats().size, 0);
+    let detached = pool.get().detach();
+    assert!(detached.is_empty());
+    assert_eq!(pool.stats().size, 0);
+
+    // Every thread has its own `LocalPool`.
+    LOCAL.get().push(1);
+    assert_eq!(LOCAL.stats().size, 1);
+    std::thread::spawn(|| {
+        assert_eq!(LOCAL.stats(), PoolStats::default());
+        LOCAL.get();
+        LOCAL.get();
+        assert_eq!(
+            LOCAL.stats(),
+            PoolStats {
+                hits: 1,
+                misses: 1,
+                size: 1
+            }
+        );
+    })
+    .join()
+    .unwrap();
+    assert_eq!(
+        LOCAL.stats(),
+        PoolStats {
+            hits: 0,
+            misses: 1,
+            size: 1
+        }
+    );
+    assert!(LOCAL.pool().get().is_empty());
+}
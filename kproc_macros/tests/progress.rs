@@ -4,6 +4,30 @@ fn tests() {
     t.pass("tests/01-explain.rs");
     t.pass("tests/02-field_iter.rs");
     t.pass("tests/03-clearable.rs");
+    t.pass("tests/04-clearable-enum.rs");
+    t.pass("tests/05-clearable-tuple.rs");
+    t.pass("tests/06-clearable-std.rs");
+    t.pass("tests/07-clearable-composite.rs");
+    t.pass("tests/08-clearable-generics.rs");
+    t.pass("tests/09-clearable-shrink.rs");
+    t.pass("tests/10-is-cleared.rs");
+    t.pass("tests/11-clearable-third-party.rs");
+    t.pass("tests/12-clearable-groups.rs");
+    t.compile_fail("tests/13-clearable-errors.rs");
+    t.pass("tests/14-clearable-with.rs");
+    t.pass("tests/15-clearable-hooks.rs");
+    t.pass("tests/16-clear-report.rs");
+    t.pass("tests/17-clearable-dyn.rs");
+    t.pass("tests/18-generational.rs");
+    t.pass("tests/19-epoch-vec.rs");
+    t.pass("tests/20-clearable-zeroize.rs");
+    t.pass("tests/21-clearable-scoped.rs");
+    t.pass("tests/22-clearable-check-default.rs");
+    t.pass("tests/23-clearable-primitives.rs");
+    t.pass("tests/24-clearable-by-name.rs");
+    t.pass("tests/25-soa.rs");
+    t.pass("tests/26-field-iter-enum.rs");
+    t.pass("tests/27-pool.rs");
     //t.pass("tests/02-create-builder.rs");
     //t.pass("tests/03-call-setters.rs");
     //t.pass("tests/04-call-build.rs");
@@ -1,47 +1,1886 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// Resetting a value in place, keeping its allocations for reuse.
+///
+/// The trait is dyn compatible, so differently typed values can be cleared together, e.g. with
+/// [`ClearRegistry`](crate::registry::ClearRegistry).
 pub trait Clearable {
     fn clear(&mut self);
 
-    fn cleared(&mut self) -> &mut Self {
+    /// Clears and additionally releases any capacity beyond `limit` elements, as with
+    /// `Vec::shrink_to`. Types without spare capacity just clear.
+    fn clear_and_shrink_to(&mut self, limit: usize) {
+        let _ = limit;
+        self.clear();
+    }
+
+    /// The largest capacity, in elements, which `clear` keeps around. Aggregates report the
+    /// maximum of their parts.
+    fn retained_capacity(&self) -> usize {
+        0
+    }
+
+    /// Hook for checking [`IsCleared`] in debug builds after a clear. Does nothing by default,
+    /// derived impls check it with `#[clearable(assert_cleared)]`.
+    fn debug_assert_cleared(&self) {}
+
+    /// Clears only the fields in the named group, as declared with `#[clearable(group = "...")]`
+    /// on derived impls, and forwards the group to nested values. Does nothing by default.
+    fn clear_group(&mut self, group: &str) {
+        let _ = group;
+    }
+
+    /// Clears the field called `name`, or a nested one with a dotted path such as
+    /// `"physics.particles"`, and returns whether it exists. Derived impls support this with
+    /// `#[clearable(by_name)]`, and the default returns `false`.
+    fn clear_field(&mut self, name: &str) -> bool {
+        let _ = name;
+        false
+    }
+
+    /// Clears like [`Clearable::clear`] and records what was dropped and retained in `report`.
+    /// Types which don't report anything just clear, shrinking if the report has a limit.
+    fn clear_with_report(&mut self, report: &mut ClearReport) {
+        match report.limit() {
+            Some(limit) => self.clear_and_shrink_to(limit),
+            None => self.clear(),
+        }
+    }
+
+    fn cleared(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
         self.clear();
+        self.debug_assert_cleared();
         self
     }
 
-    fn cleared_if(&mut self, clear: bool) -> &mut Self {
+    fn cleared_if(&mut self, clear: bool) -> &mut Self
+    where
+        Self: Sized,
+    {
         if clear {
             self.clear();
+            self.debug_assert_cleared();
         }
         self
     }
+
+    /// Borrows the value behind a guard which clears it when dropped, including on early returns
+    /// and unwinding. `kmacros::cleared_scope!` is a shorthand for this.
+    ///
+    /// ```
+    /// use kmacros::Clearable;
+    ///
+    /// let mut buffer = Vec::new();
+    /// for line in ["a", "b"] {
+    ///     let mut buffer = buffer.scoped();
+    ///     buffer.push(line);
+    ///     assert_eq!(buffer.len(), 1);
+    /// }
+    /// assert!(buffer.is_empty());
+    /// ```
+    fn scoped(&mut self) -> ClearOnDrop<'_, Self>
+    where
+        Self: Sized,
+    {
+        ClearOnDrop::new(self)
+    }
 }
 
-#[cfg(not(feature = "no_std"))]
-mod impls {
-    use super::*;
+/// Guard from [`Clearable::scoped`] which clears the value on drop.
+pub struct ClearOnDrop<'a, T: Clearable + ?Sized> {
+    value: &'a mut T,
+}
 
-    impl<T> Clearable for Vec<T> {
-        fn clear(&mut self) {
-            Vec::clear(self);
+impl<'a, T: Clearable + ?Sized> ClearOnDrop<'a, T> {
+    /// Like [`Clearable::scoped`], but also for unsized values such as `dyn Clearable`.
+    pub fn new(value: &'a mut T) -> Self {
+        Self { value }
+    }
+}
+
+impl<T: Clearable + ?Sized> core::ops::Deref for ClearOnDrop<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T: Clearable + ?Sized> core::ops::DerefMut for ClearOnDrop<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<T: Clearable + core::fmt::Debug + ?Sized> core::fmt::Debug for ClearOnDrop<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T: Clearable + ?Sized> Drop for ClearOnDrop<'_, T> {
+    fn drop(&mut self) {
+        self.value.clear();
+    }
+}
+
+/// Whether a value is in the state [`Clearable::clear`] leaves it in.
+///
+/// Derived impls understand the same `#[clearable(...)]` attributes as the `Clearable` derive:
+/// skipped fields and fields reset with `with`, `value` or an expression aren't checked, and
+/// `default` fields are compared to `Default::default()`.
+pub trait IsCleared {
+    fn is_cleared(&self) -> bool;
+}
+
+/// Clearing through a shared reference, for types with interior mutability.
+///
+/// This is what allows `Rc<T>` and `Arc<T>` to be cleared, e.g. `Arc<Mutex<Vec<T>>>`. The other
+/// methods mirror those of [`Clearable`], so that `Rc<T>` and `Arc<T>` can forward all of them.
+pub trait SharedClearable {
+    fn clear_shared(&self);
+
+    /// Like [`Clearable::clear_and_shrink_to`]. Just clears by default.
+    fn clear_and_shrink_to_shared(&self, limit: usize) {
+        let _ = limit;
+        self.clear_shared();
+    }
+
+    /// Like [`Clearable::retained_capacity`].
+    fn retained_capacity_shared(&self) -> usize {
+        0
+    }
+
+    /// Like [`Clearable::debug_assert_cleared`].
+    fn debug_assert_cleared_shared(&self) {}
+
+    /// Like [`Clearable::clear_group`].
+    fn clear_group_shared(&self, group: &str) {
+        let _ = group;
+    }
+
+    /// Like [`Clearable::clear_field`].
+    fn clear_field_shared(&self, name: &str) -> bool {
+        let _ = name;
+        false
+    }
+
+    /// Like [`Clearable::clear_with_report`].
+    fn clear_with_report_shared(&self, report: &mut ClearReport) {
+        match report.limit() {
+            Some(limit) => self.clear_and_shrink_to_shared(limit),
+            None => self.clear_shared(),
+        }
+    }
+}
+
+/// Clearing which also wipes the old contents from memory, for keys and tokens.
+///
+/// The whole allocation, including spare capacity, is overwritten with zeros using volatile
+/// writes followed by a compiler fence, so the writes can't be optimized away. Copies left behind
+/// by earlier reallocations aren't reachable and can't be wiped. Used by `#[clearable(zeroize)]`
+/// fields.
+///
+/// ```
+/// use kmacros::SecureClearable;
+///
+/// let mut key = b"hunter2".to_vec();
+/// key.secure_clear();
+/// assert!(key.is_empty());
+///
+/// let mut block = [0xffu8; 16];
+/// block.secure_clear();
+/// assert_eq!(block, [0; 16]);
+/// ```
+pub trait SecureClearable {
+    fn secure_clear(&mut self);
+}
+
+/// Zeroes `len` bytes at `ptr` with volatile writes, which the compiler can't elide as dead
+/// stores.
+///
+/// # Safety
+///
+/// `ptr` must be valid for writes of `len` bytes.
+unsafe fn zero_volatile(ptr: *mut u8, len: usize) {
+    for i in 0..len {
+        core::ptr::write_volatile(ptr.add(i), 0);
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// What [`Clearable::clear_with_report`] dropped and retained, per field.
+///
+/// Derived impls [`enter`](ClearReport::enter) each field, so entries have dotted paths such as
+/// `inner.items`. A `shrink_to` on the field applies while it is entered, which is why containers
+/// report through [`ClearReport::clear_and_record`].
+///
+/// Without the `alloc` feature only the totals are kept, and there is nothing to shrink.
+///
+/// ```
+/// use kmacros::{ClearReport, Clearable};
+///
+/// let mut buffers = (vec![1u32, 2, 3], String::from("hello"));
+/// let mut report = ClearReport::new();
+/// buffers.clear_with_report(&mut report);
+/// assert_eq!(report.entries()[0].path, "0");
+/// assert_eq!(report.entries()[0].dropped, 3);
+/// assert!(report.entries()[0].retained_bytes >= 12);
+/// assert_eq!(report.dropped(), 8);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ClearReport {
+    #[cfg(feature = "alloc")]
+    entries: Vec<ClearEntry>,
+    #[cfg(feature = "alloc")]
+    path: String,
+    #[cfg(feature = "alloc")]
+    limit: Option<usize>,
+    /// Path length and limit to restore on `leave`.
+    #[cfg(feature = "alloc")]
+    scopes: Vec<(usize, Option<usize>)>,
+    #[cfg(not(feature = "alloc"))]
+    dropped: usize,
+    #[cfg(not(feature = "alloc"))]
+    retained_bytes: usize,
+    #[cfg(not(feature = "alloc"))]
+    depth: usize,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClearEntry {
+    /// Dotted path of the field, empty for the value the report was started on.
+    pub path: String,
+    /// Number of elements dropped by the clear.
+    pub dropped: usize,
+    /// Capacity kept after the clear, in bytes. Approximate for hash tables.
+    pub retained_bytes: usize,
+    /// The enum variant which was replaced by the reset variant, for entries recorded with
+    /// [`ClearReport::record_replaced`]. Its fields aren't counted in `dropped`.
+    pub replaced: Option<&'static str>,
+}
+
+#[cfg(feature = "alloc")]
+impl ClearReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> &[ClearEntry] {
+        &self.entries
+    }
+
+    /// Total number of elements dropped.
+    pub fn dropped(&self) -> usize {
+        self.entries.iter().map(|e| e.dropped).sum()
+    }
+
+    /// Total capacity retained, in bytes.
+    pub fn retained_bytes(&self) -> usize {
+        self.entries.iter().map(|e| e.retained_bytes).sum()
+    }
+
+    /// Appends `name` to the path of the following entries, until the matching `leave`.
+    pub fn enter(&mut self, name: impl core::fmt::Display) {
+        use core::fmt::Write;
+
+        self.scopes.push((self.path.len(), self.limit));
+        if !self.path.is_empty() {
+            self.path.push('.');
+        }
+        let _ = write!(self.path, "{}", name);
+    }
+
+    /// Like `enter`, but values inside are also shrunk to at most `limit` elements.
+    pub fn enter_with_limit(&mut self, name: impl core::fmt::Display, limit: usize) {
+        self.enter(name);
+        self.limit = Some(self.limit.map_or(limit, |outer| outer.min(limit)));
+    }
+
+    /// Shrinks values to at most `limit` elements until the matching `leave`, keeping the path.
+    pub fn enter_limit(&mut self, limit: usize) {
+        self.scopes.push((self.path.len(), self.limit));
+        self.limit = Some(self.limit.map_or(limit, |outer| outer.min(limit)));
+    }
+
+    pub fn leave(&mut self) {
+        let (len, limit) = self.scopes.pop().expect("leave without a matching enter");
+        self.path.truncate(len);
+        self.limit = limit;
+    }
+
+    /// The shrink limit of the current path, if any.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn record(&mut self, dropped: usize, retained_bytes: usize) {
+        self.entries.push(ClearEntry {
+            path: self.path.clone(),
+            dropped,
+            retained_bytes,
+            replaced: None,
+        });
+    }
+
+    /// Records that an enum switched from `variant` to its reset variant, dropping the fields of
+    /// `variant` uncounted.
+    pub fn record_replaced(&mut self, variant: &'static str) {
+        self.entries.push(ClearEntry {
+            path: self.path.clone(),
+            dropped: 0,
+            retained_bytes: 0,
+            replaced: Some(variant),
+        });
+    }
+}
+
+/// Totals only: there are no paths to store and no containers to shrink.
+#[cfg(not(feature = "alloc"))]
+impl ClearReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total number of elements dropped.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Total capacity retained, in bytes.
+    pub fn retained_bytes(&self) -> usize {
+        self.retained_bytes
+    }
+
+    pub fn enter(&mut self, _name: impl core::fmt::Display) {
+        self.depth += 1;
+    }
+
+    pub fn enter_with_limit(&mut self, name: impl core::fmt::Display, _limit: usize) {
+        self.enter(name);
+    }
+
+    pub fn enter_limit(&mut self, _limit: usize) {
+        self.depth += 1;
+    }
+
+    pub fn leave(&mut self) {
+        self.depth = self
+            .depth
+            .checked_sub(1)
+            .expect("leave without a matching enter");
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        None
+    }
+
+    pub fn record(&mut self, dropped: usize, retained_bytes: usize) {
+        self.dropped += dropped;
+        self.retained_bytes += retained_bytes;
+    }
+
+    pub fn record_replaced(&mut self, _variant: &'static str) {}
+}
+
+impl ClearReport {
+    /// Clears `value`, with `clear_and_shrink_to` if the current path has a limit, then records
+    /// `dropped` together with `retained_bytes` of the cleared value.
+    pub fn clear_and_record<T: Clearable + ?Sized>(
+        &mut self,
+        value: &mut T,
+        dropped: usize,
+        retained_bytes: impl FnOnce(&T) -> usize,
+    ) {
+        match self.limit() {
+            Some(limit) => value.clear_and_shrink_to(limit),
+            None => value.clear(),
+        }
+        let retained_bytes = retained_bytes(value);
+        self.record(dropped, retained_bytes);
+    }
+}
+
+/// Drops the entries, keeping their allocation for the next report.
+impl Clearable for ClearReport {
+    #[cfg(feature = "alloc")]
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.path.clear();
+        self.limit = None;
+        self.scopes.clear();
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Adaptive shrinking: clears normally, but once the value has been oversized (retaining more
+/// than `limit` elements of capacity) for `patience` clears in a row, the next clear shrinks it
+/// back down with [`Clearable::clear_and_shrink_to`].
+///
+/// Since derived impls forward `clear_and_shrink_to` to their fields, wrapping a derived struct
+/// applies the policy to every buffer inside it.
+///
+/// ```
+/// use kmacros::{Clearable, HighWatermark};
+///
+/// let mut buffer = HighWatermark::new(Vec::<u8>::new(), 16, 2);
+/// buffer.reserve(1024);
+/// buffer.clear();
+/// assert!(buffer.capacity() >= 1024);
+/// buffer.clear();
+/// assert!(buffer.capacity() <= 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HighWatermark<T> {
+    value: T,
+    limit: usize,
+    patience: u32,
+    oversized: u32,
+}
+
+impl<T> HighWatermark<T> {
+    pub fn new(value: T, limit: usize, patience: u32) -> Self {
+        Self {
+            value,
+            limit,
+            patience,
+            oversized: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> core::ops::Deref for HighWatermark<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> core::ops::DerefMut for HighWatermark<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: Clearable> HighWatermark<T> {
+    /// Counts the clear towards `patience`, returning whether it should shrink.
+    fn should_shrink(&mut self) -> bool {
+        if self.value.retained_capacity() > self.limit {
+            self.oversized += 1;
+        } else {
+            self.oversized = 0;
+        }
+        let shrink = self.oversized >= self.patience;
+        if shrink {
+            self.oversized = 0;
+        }
+        shrink
+    }
+}
+
+impl<T: Clearable> Clearable for HighWatermark<T> {
+    fn clear(&mut self) {
+        if self.should_shrink() {
+            self.value.clear_and_shrink_to(self.limit);
+        } else {
+            self.value.clear();
+        }
+    }
+
+    fn clear_and_shrink_to(&mut self, limit: usize) {
+        self.oversized = 0;
+        self.value.clear_and_shrink_to(limit);
+    }
+
+    fn retained_capacity(&self) -> usize {
+        self.value.retained_capacity()
+    }
+
+    fn clear_group(&mut self, group: &str) {
+        self.value.clear_group(group);
+    }
+
+    fn clear_field(&mut self, name: &str) -> bool {
+        self.value.clear_field(name)
+    }
+
+    fn clear_with_report(&mut self, report: &mut ClearReport) {
+        if self.should_shrink() {
+            report.enter_limit(self.limit);
+            self.value.clear_with_report(report);
+            report.leave();
+        } else {
+            self.value.clear_with_report(report);
+        }
+    }
+
+    fn debug_assert_cleared(&self) {
+        self.value.debug_assert_cleared();
+    }
+}
+
+impl<T: IsCleared> IsCleared for HighWatermark<T> {
+    fn is_cleared(&self) -> bool {
+        self.value.is_cleared()
+    }
+}
+
+/// Detects stale handles: every clear bumps a generation counter, and [`GenHandle`]s created
+/// before it no longer resolve. Clearing a group or a field counts, even if the handle points
+/// elsewhere.
+///
+/// Reading goes through `Deref`. There is no `DerefMut`, so that the value can't be cleared
+/// without bumping the generation; [`Generational::value_mut`] is the explicit escape hatch.
+///
+/// ```
+/// use kmacros::{Clearable, Generational};
+///
+/// let mut nodes = Generational::new(Vec::new());
+/// let root = nodes.push("root");
+/// assert_eq!(nodes.get(root), Some(&"root"));
+///
+/// nodes.clear();
+/// let other = nodes.push("other");
+/// assert_eq!(nodes.get(root), None);
+/// assert_eq!(nodes.get(other), Some(&"other"));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Generational<T> {
+    value: T,
+    generation: u64,
+}
+
+/// A key into a [`Generational`] value, only valid until it is cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenHandle<K> {
+    key: K,
+    generation: u64,
+}
+
+impl<K> GenHandle<K> {
+    /// The key, without checking that it is still valid.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl<T> Generational<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            generation: 0,
+        }
+    }
+
+    /// Number of clears so far, including clears of a group or a single field.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// A handle for `key` in the current generation.
+    pub fn handle<K>(&self, key: K) -> GenHandle<K> {
+        GenHandle {
+            key,
+            generation: self.generation,
+        }
+    }
+
+    pub fn is_current<K>(&self, handle: &GenHandle<K>) -> bool {
+        handle.generation == self.generation
+    }
+
+    /// The key of `handle`, or `None` if the value was cleared since it was created.
+    pub fn resolve<K>(&self, handle: GenHandle<K>) -> Option<K> {
+        self.is_current(&handle).then_some(handle.key)
+    }
+
+    /// Mutable access which doesn't track clears: clearing through it keeps old handles valid.
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> core::ops::Deref for Generational<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Clearable> Clearable for Generational<T> {
+    fn clear(&mut self) {
+        self.value.clear();
+        self.generation += 1;
+    }
+
+    fn clear_and_shrink_to(&mut self, limit: usize) {
+        self.value.clear_and_shrink_to(limit);
+        self.generation += 1;
+    }
+
+    fn retained_capacity(&self) -> usize {
+        self.value.retained_capacity()
+    }
+
+    /// Invalidates handles, since the group may hold the data they point to.
+    fn clear_group(&mut self, group: &str) {
+        self.value.clear_group(group);
+        self.generation += 1;
+    }
+
+    /// Invalidates handles if the field was found and cleared.
+    fn clear_field(&mut self, name: &str) -> bool {
+        let cleared = self.value.clear_field(name);
+        if cleared {
+            self.generation += 1;
         }
+        cleared
+    }
+
+    fn clear_with_report(&mut self, report: &mut ClearReport) {
+        self.value.clear_with_report(report);
+        self.generation += 1;
+    }
+
+    fn debug_assert_cleared(&self) {
+        self.value.debug_assert_cleared();
+    }
+}
+
+impl<T: IsCleared> IsCleared for Generational<T> {
+    fn is_cleared(&self) -> bool {
+        self.value.is_cleared()
+    }
+}
+
+/// Index handles for vectors.
+#[cfg(feature = "alloc")]
+impl<T> Generational<Vec<T>> {
+    pub fn push(&mut self, value: T) -> GenHandle<usize> {
+        self.value.push(value);
+        self.handle(self.value.len() - 1)
+    }
+
+    pub fn get(&self, handle: GenHandle<usize>) -> Option<&T> {
+        self.value.get(self.resolve(handle)?)
+    }
+
+    pub fn get_mut(&mut self, handle: GenHandle<usize>) -> Option<&mut T> {
+        let index = self.resolve(handle)?;
+        self.value.get_mut(index)
     }
+}
 
-    use std::collections::HashMap;
-    use std::collections::HashSet;
+mod core_impls {
+    use super::*;
+    use core::cell::{Cell, RefCell};
+    use core::sync::atomic::{self, Ordering};
 
-    impl<K, V> Clearable for HashMap<K, V> {
+    impl<T: Clearable> Clearable for [T] {
         fn clear(&mut self) {
-            HashMap::clear(self);
+            for x in self {
+                x.clear();
+            }
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            for x in self {
+                x.clear_and_shrink_to(limit);
+            }
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.iter().map(T::retained_capacity).max().unwrap_or(0)
+        }
+
+        fn clear_group(&mut self, group: &str) {
+            for x in self {
+                x.clear_group(group);
+            }
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            for (i, x) in self.iter_mut().enumerate() {
+                report.enter(i);
+                x.clear_with_report(report);
+                report.leave();
+            }
         }
     }
 
-    impl<K> Clearable for HashSet<K> {
+    impl<T: Clearable, const N: usize> Clearable for [T; N] {
         fn clear(&mut self) {
-            HashSet::clear(self);
+            self.as_mut_slice().clear();
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            self.as_mut_slice().clear_and_shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.as_slice().retained_capacity()
+        }
+
+        fn clear_group(&mut self, group: &str) {
+            self.as_mut_slice().clear_group(group);
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            self.as_mut_slice().clear_with_report(report);
         }
     }
 
-    impl<T> Clearable for Option<T> {
+    macro_rules! tuple_impls {
+        ($($name:ident)+) => {
+            impl<$($name: Clearable),+> Clearable for ($($name,)+) {
+                #[allow(non_snake_case)]
+                fn clear(&mut self) {
+                    let ($($name,)+) = self;
+                    $($name.clear();)+
+                }
+
+                #[allow(non_snake_case)]
+                fn clear_and_shrink_to(&mut self, limit: usize) {
+                    let ($($name,)+) = self;
+                    $($name.clear_and_shrink_to(limit);)+
+                }
+
+                #[allow(non_snake_case)]
+                fn retained_capacity(&self) -> usize {
+                    let ($($name,)+) = self;
+                    let capacity = 0;
+                    $(let capacity = capacity.max($name.retained_capacity());)+
+                    capacity
+                }
+
+                #[allow(non_snake_case)]
+                fn clear_group(&mut self, group: &str) {
+                    let ($($name,)+) = self;
+                    $($name.clear_group(group);)+
+                }
+
+                #[allow(non_snake_case)]
+                fn clear_with_report(&mut self, report: &mut ClearReport) {
+                    let ($($name,)+) = self;
+                    let mut index = 0;
+                    $(
+                        report.enter(index);
+                        $name.clear_with_report(report);
+                        report.leave();
+                        index += 1;
+                    )+
+                    let _ = index;
+                }
+            }
+        };
+    }
+
+    tuple_impls! { A }
+    tuple_impls! { A B }
+    tuple_impls! { A B C }
+    tuple_impls! { A B C D }
+    tuple_impls! { A B C D E }
+    tuple_impls! { A B C D E F }
+    tuple_impls! { A B C D E F G }
+    tuple_impls! { A B C D E F G H }
+    tuple_impls! { A B C D E F G H I }
+    tuple_impls! { A B C D E F G H I J }
+    tuple_impls! { A B C D E F G H I J K }
+    tuple_impls! { A B C D E F G H I J K L }
+
+    impl SecureClearable for [u8] {
+        fn secure_clear(&mut self) {
+            // SAFETY: writes stay within the slice.
+            unsafe { zero_volatile(self.as_mut_ptr(), self.len()) };
+        }
+    }
+
+    impl<const N: usize> SecureClearable for [u8; N] {
+        fn secure_clear(&mut self) {
+            self.as_mut_slice().secure_clear();
+        }
+    }
+
+    /// Plain values are reset to their default, so they can be mixed with buffers in derived
+    /// structs without `#[clearable(default)]`.
+    macro_rules! default_impls {
+        ($($ty:ty),+ $(,)?) => {
+            $(
+                impl Clearable for $ty {
+                    fn clear(&mut self) {
+                        *self = Default::default();
+                    }
+                }
+
+                impl IsCleared for $ty {
+                    fn is_cleared(&self) -> bool {
+                        *self == Default::default()
+                    }
+                }
+            )+
+        };
+    }
+
+    default_impls! {
+        u8, u16, u32, u64, u128, usize,
+        i8, i16, i32, i64, i128, isize,
+        f32, f64, bool, char, (),
+    }
+
+    impl<T: ?Sized> Clearable for core::marker::PhantomData<T> {
+        fn clear(&mut self) {}
+    }
+
+    impl<T: ?Sized> IsCleared for core::marker::PhantomData<T> {
+        fn is_cleared(&self) -> bool {
+            true
+        }
+    }
+
+    impl<T: Default> Clearable for Cell<T> {
         fn clear(&mut self) {
-            *self = None;
+            *self.get_mut() = T::default();
+        }
+    }
+
+    impl<T: Default> SharedClearable for Cell<T> {
+        fn clear_shared(&self) {
+            self.set(T::default());
+        }
+    }
+
+    impl<T: Clearable + ?Sized> Clearable for RefCell<T> {
+        fn clear(&mut self) {
+            self.get_mut().clear();
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            self.get_mut().clear_and_shrink_to(limit);
+        }
+
+        /// Returns 0 if the value is currently mutably borrowed.
+        fn retained_capacity(&self) -> usize {
+            self.try_borrow().map_or(0, |x| x.retained_capacity())
+        }
+
+        fn clear_group(&mut self, group: &str) {
+            self.get_mut().clear_group(group);
+        }
+
+        fn clear_field(&mut self, name: &str) -> bool {
+            self.get_mut().clear_field(name)
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            self.get_mut().clear_with_report(report);
+        }
+
+        /// Skips the check if the value is currently mutably borrowed.
+        fn debug_assert_cleared(&self) {
+            if let Ok(x) = self.try_borrow() {
+                x.debug_assert_cleared();
+            }
+        }
+    }
+
+    /// Panics if the value is currently borrowed, except for `retained_capacity_shared` and
+    /// `debug_assert_cleared_shared`.
+    impl<T: Clearable + ?Sized> SharedClearable for RefCell<T> {
+        fn clear_shared(&self) {
+            self.borrow_mut().clear();
+        }
+
+        fn clear_and_shrink_to_shared(&self, limit: usize) {
+            self.borrow_mut().clear_and_shrink_to(limit);
+        }
+
+        fn retained_capacity_shared(&self) -> usize {
+            self.try_borrow().map_or(0, |x| x.retained_capacity())
+        }
+
+        fn debug_assert_cleared_shared(&self) {
+            if let Ok(x) = self.try_borrow() {
+                x.debug_assert_cleared();
+            }
+        }
+
+        fn clear_group_shared(&self, group: &str) {
+            self.borrow_mut().clear_group(group);
+        }
+
+        fn clear_field_shared(&self, name: &str) -> bool {
+            self.borrow_mut().clear_field(name)
+        }
+
+        fn clear_with_report_shared(&self, report: &mut ClearReport) {
+            self.borrow_mut().clear_with_report(report);
+        }
+    }
+
+    macro_rules! atomic_impls {
+        ($($size:literal => $($atomic:ident = $zero:literal),+;)+) => {
+            $($(
+                #[cfg(target_has_atomic = $size)]
+                impl Clearable for atomic::$atomic {
+                    fn clear(&mut self) {
+                        *self.get_mut() = $zero;
+                    }
+                }
+
+                #[cfg(target_has_atomic = $size)]
+                impl SharedClearable for atomic::$atomic {
+                    fn clear_shared(&self) {
+                        self.store($zero, Ordering::SeqCst);
+                    }
+                }
+            )+)+
+        };
+    }
+
+    atomic_impls! {
+        "8" => AtomicBool = false, AtomicU8 = 0, AtomicI8 = 0;
+        "16" => AtomicU16 = 0, AtomicI16 = 0;
+        "32" => AtomicU32 = 0, AtomicI32 = 0;
+        "64" => AtomicU64 = 0, AtomicI64 = 0;
+        "ptr" => AtomicUsize = 0, AtomicIsize = 0;
+    }
+
+    impl<T: IsCleared> IsCleared for [T] {
+        fn is_cleared(&self) -> bool {
+            self.iter().all(T::is_cleared)
+        }
+    }
+
+    impl<T: IsCleared, const N: usize> IsCleared for [T; N] {
+        fn is_cleared(&self) -> bool {
+            self.as_slice().is_cleared()
+        }
+    }
+
+    macro_rules! tuple_is_cleared_impls {
+        ($($name:ident)+) => {
+            impl<$($name: IsCleared),+> IsCleared for ($($name,)+) {
+                #[allow(non_snake_case)]
+                fn is_cleared(&self) -> bool {
+                    let ($($name,)+) = self;
+                    true $(&& $name.is_cleared())+
+                }
+            }
+        };
+    }
+
+    tuple_is_cleared_impls! { A }
+    tuple_is_cleared_impls! { A B }
+    tuple_is_cleared_impls! { A B C }
+    tuple_is_cleared_impls! { A B C D }
+    tuple_is_cleared_impls! { A B C D E }
+    tuple_is_cleared_impls! { A B C D E F }
+    tuple_is_cleared_impls! { A B C D E F G }
+    tuple_is_cleared_impls! { A B C D E F G H }
+    tuple_is_cleared_impls! { A B C D E F G H I }
+    tuple_is_cleared_impls! { A B C D E F G H I J }
+    tuple_is_cleared_impls! { A B C D E F G H I J K }
+    tuple_is_cleared_impls! { A B C D E F G H I J K L }
+
+    impl<T: Default + PartialEq + Copy> IsCleared for Cell<T> {
+        fn is_cleared(&self) -> bool {
+            self.get() == T::default()
+        }
+    }
+
+    impl<T: IsCleared + ?Sized> IsCleared for RefCell<T> {
+        /// Panics if the value is currently mutably borrowed.
+        fn is_cleared(&self) -> bool {
+            self.borrow().is_cleared()
+        }
+    }
+
+    macro_rules! atomic_is_cleared_impls {
+        ($($size:literal => $($atomic:ident = $zero:literal),+;)+) => {
+            $($(
+                #[cfg(target_has_atomic = $size)]
+                impl IsCleared for atomic::$atomic {
+                    fn is_cleared(&self) -> bool {
+                        self.load(Ordering::SeqCst) == $zero
+                    }
+                }
+            )+)+
+        };
+    }
+
+    atomic_is_cleared_impls! {
+        "8" => AtomicBool = false, AtomicU8 = 0, AtomicI8 = 0;
+        "16" => AtomicU16 = 0, AtomicI16 = 0;
+        "32" => AtomicU32 = 0, AtomicI32 = 0;
+        "64" => AtomicU64 = 0, AtomicI64 = 0;
+        "ptr" => AtomicUsize = 0, AtomicIsize = 0;
+    }
+
+    impl<T> Clearable for Option<T> {
+        fn clear(&mut self) {
+            *self = None;
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = usize::from(self.is_some());
+            report.clear_and_record(self, dropped, |_| 0);
+        }
+    }
+
+    impl<T> IsCleared for Option<T> {
+        fn is_cleared(&self) -> bool {
+            self.is_none()
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::*;
+    use alloc::boxed::Box;
+    use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, LinkedList, VecDeque};
+    use alloc::rc::Rc;
+    use alloc::sync::Arc;
+
+    impl<T> Clearable for Vec<T> {
+        fn clear(&mut self) {
+            Vec::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            Vec::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| x.capacity() * core::mem::size_of::<T>());
+        }
+    }
+
+    impl Clearable for String {
+        fn clear(&mut self) {
+            String::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            String::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| x.capacity());
+        }
+    }
+
+    impl<T> Clearable for VecDeque<T> {
+        fn clear(&mut self) {
+            VecDeque::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            VecDeque::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| x.capacity() * core::mem::size_of::<T>());
+        }
+    }
+
+    impl<K, V> Clearable for BTreeMap<K, V> {
+        fn clear(&mut self) {
+            BTreeMap::clear(self);
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |_| 0);
+        }
+    }
+
+    impl<K> Clearable for BTreeSet<K> {
+        fn clear(&mut self) {
+            BTreeSet::clear(self);
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |_| 0);
+        }
+    }
+
+    impl<T> Clearable for BinaryHeap<T> {
+        fn clear(&mut self) {
+            BinaryHeap::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            BinaryHeap::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| x.capacity() * core::mem::size_of::<T>());
+        }
+    }
+
+    impl<T> Clearable for LinkedList<T> {
+        fn clear(&mut self) {
+            LinkedList::clear(self);
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |_| 0);
+        }
+    }
+
+    /// Keeps the capacity, zeroed.
+    impl SecureClearable for Vec<u8> {
+        fn secure_clear(&mut self) {
+            // SAFETY: writes stay within the allocation, and zeros are valid bytes.
+            unsafe { zero_volatile(self.as_mut_ptr(), self.capacity()) };
+            Vec::clear(self);
+        }
+    }
+
+    /// Keeps the capacity, zeroed.
+    impl SecureClearable for String {
+        fn secure_clear(&mut self) {
+            // SAFETY: the string is empty, and so valid UTF-8, once this returns.
+            unsafe { self.as_mut_vec() }.secure_clear();
+        }
+    }
+
+    impl<T: Clearable + ?Sized> Clearable for Box<T> {
+        fn clear(&mut self) {
+            (**self).clear();
+        }
+
+        fn debug_assert_cleared(&self) {
+            (**self).debug_assert_cleared();
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            (**self).clear_and_shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            (**self).retained_capacity()
+        }
+
+        fn clear_group(&mut self, group: &str) {
+            (**self).clear_group(group);
+        }
+
+        fn clear_field(&mut self, name: &str) -> bool {
+            (**self).clear_field(name)
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            (**self).clear_with_report(report);
+        }
+    }
+
+    impl<T: SharedClearable + ?Sized> Clearable for Rc<T> {
+        fn clear(&mut self) {
+            (**self).clear_shared();
+        }
+
+        fn debug_assert_cleared(&self) {
+            (**self).debug_assert_cleared_shared();
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            (**self).clear_and_shrink_to_shared(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            (**self).retained_capacity_shared()
+        }
+
+        fn clear_group(&mut self, group: &str) {
+            (**self).clear_group_shared(group);
+        }
+
+        fn clear_field(&mut self, name: &str) -> bool {
+            (**self).clear_field_shared(name)
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            (**self).clear_with_report_shared(report);
+        }
+    }
+
+    impl<T: SharedClearable + ?Sized> Clearable for Arc<T> {
+        fn clear(&mut self) {
+            (**self).clear_shared();
+        }
+
+        fn debug_assert_cleared(&self) {
+            (**self).debug_assert_cleared_shared();
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            (**self).clear_and_shrink_to_shared(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            (**self).retained_capacity_shared()
+        }
+
+        fn clear_group(&mut self, group: &str) {
+            (**self).clear_group_shared(group);
+        }
+
+        fn clear_field(&mut self, name: &str) -> bool {
+            (**self).clear_field_shared(name)
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            (**self).clear_with_report_shared(report);
+        }
+    }
+
+    impl<T> IsCleared for Vec<T> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl IsCleared for String {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<T> IsCleared for VecDeque<T> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<K, V> IsCleared for BTreeMap<K, V> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<K> IsCleared for BTreeSet<K> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<T> IsCleared for BinaryHeap<T> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<T> IsCleared for LinkedList<T> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<T: IsCleared + ?Sized> IsCleared for Box<T> {
+        fn is_cleared(&self) -> bool {
+            (**self).is_cleared()
+        }
+    }
+
+    impl<T: IsCleared + ?Sized> IsCleared for Rc<T> {
+        fn is_cleared(&self) -> bool {
+            (**self).is_cleared()
+        }
+    }
+
+    impl<T: IsCleared + ?Sized> IsCleared for Arc<T> {
+        fn is_cleared(&self) -> bool {
+            (**self).is_cleared()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_impls {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+    use std::ffi::OsString;
+    use std::hash::{BuildHasher, Hash};
+    use std::path::PathBuf;
+    use std::sync::{Mutex, PoisonError, RwLock};
+
+    impl<K: Eq + Hash, V, S: BuildHasher> Clearable for HashMap<K, V, S> {
+        fn clear(&mut self) {
+            HashMap::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            HashMap::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| {
+                x.capacity() * core::mem::size_of::<(K, V)>()
+            });
+        }
+    }
+
+    impl<K: Eq + Hash, S: BuildHasher> Clearable for HashSet<K, S> {
+        fn clear(&mut self) {
+            HashSet::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            HashSet::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| x.capacity() * core::mem::size_of::<K>());
+        }
+    }
+
+    impl Clearable for OsString {
+        fn clear(&mut self) {
+            OsString::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            OsString::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| x.capacity());
+        }
+    }
+
+    impl Clearable for PathBuf {
+        fn clear(&mut self) {
+            self.as_mut_os_string().clear();
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            self.as_mut_os_string().clear_and_shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.as_os_str().len();
+            report.clear_and_record(self, dropped, |x| x.capacity());
+        }
+    }
+
+    /// A poisoned lock is still cleared.
+    impl<T: Clearable + ?Sized> Clearable for Mutex<T> {
+        fn clear(&mut self) {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_and_shrink_to(limit);
+        }
+
+        /// Returns 0 if the lock is currently held.
+        fn retained_capacity(&self) -> usize {
+            self.try_lock().map_or(0, |x| x.retained_capacity())
+        }
+
+        fn clear_group(&mut self, group: &str) {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_group(group);
+        }
+
+        fn clear_field(&mut self, name: &str) -> bool {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_field(name)
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_with_report(report);
+        }
+
+        fn debug_assert_cleared(&self) {
+            self.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .debug_assert_cleared();
+        }
+    }
+
+    impl<T: Clearable + ?Sized> SharedClearable for Mutex<T> {
+        fn clear_shared(&self) {
+            self.lock().unwrap_or_else(PoisonError::into_inner).clear();
+        }
+
+        fn clear_and_shrink_to_shared(&self, limit: usize) {
+            self.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_and_shrink_to(limit);
+        }
+
+        /// Returns 0 if the lock is currently held.
+        fn retained_capacity_shared(&self) -> usize {
+            self.try_lock().map_or(0, |x| x.retained_capacity())
+        }
+
+        fn debug_assert_cleared_shared(&self) {
+            self.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .debug_assert_cleared();
+        }
+
+        fn clear_group_shared(&self, group: &str) {
+            self.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_group(group);
+        }
+
+        fn clear_field_shared(&self, name: &str) -> bool {
+            self.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_field(name)
+        }
+
+        fn clear_with_report_shared(&self, report: &mut ClearReport) {
+            self.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_with_report(report);
+        }
+    }
+
+    impl<T: Clearable + ?Sized> Clearable for RwLock<T> {
+        fn clear(&mut self) {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_and_shrink_to(limit);
+        }
+
+        /// Returns 0 if the lock is currently held.
+        fn retained_capacity(&self) -> usize {
+            self.try_read().map_or(0, |x| x.retained_capacity())
+        }
+
+        fn clear_group(&mut self, group: &str) {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_group(group);
+        }
+
+        fn clear_field(&mut self, name: &str) -> bool {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_field(name)
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            self.get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_with_report(report);
+        }
+
+        fn debug_assert_cleared(&self) {
+            self.read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .debug_assert_cleared();
+        }
+    }
+
+    impl<T: Clearable + ?Sized> SharedClearable for RwLock<T> {
+        fn clear_shared(&self) {
+            self.write().unwrap_or_else(PoisonError::into_inner).clear();
+        }
+
+        fn clear_and_shrink_to_shared(&self, limit: usize) {
+            self.write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_and_shrink_to(limit);
+        }
+
+        /// Returns 0 if the lock is currently held.
+        fn retained_capacity_shared(&self) -> usize {
+            self.try_read().map_or(0, |x| x.retained_capacity())
+        }
+
+        fn debug_assert_cleared_shared(&self) {
+            self.read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .debug_assert_cleared();
+        }
+
+        fn clear_group_shared(&self, group: &str) {
+            self.write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_group(group);
+        }
+
+        fn clear_field_shared(&self, name: &str) -> bool {
+            self.write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_field(name)
+        }
+
+        fn clear_with_report_shared(&self, report: &mut ClearReport) {
+            self.write()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear_with_report(report);
+        }
+    }
+
+    impl<K, V, S> IsCleared for HashMap<K, V, S> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<K, S> IsCleared for HashSet<K, S> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl IsCleared for OsString {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl IsCleared for PathBuf {
+        fn is_cleared(&self) -> bool {
+            self.as_os_str().is_empty()
+        }
+    }
+
+    impl<T: IsCleared + ?Sized> IsCleared for Mutex<T> {
+        fn is_cleared(&self) -> bool {
+            self.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .is_cleared()
+        }
+    }
+
+    impl<T: IsCleared + ?Sized> IsCleared for RwLock<T> {
+        fn is_cleared(&self) -> bool {
+            self.read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .is_cleared()
+        }
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+mod hashbrown_impls {
+    use super::*;
+    use core::hash::{BuildHasher, Hash};
+    use hashbrown::{HashMap, HashSet};
+
+    impl<K: Eq + Hash, V, S: BuildHasher> Clearable for HashMap<K, V, S> {
+        fn clear(&mut self) {
+            HashMap::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            HashMap::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| {
+                x.capacity() * core::mem::size_of::<(K, V)>()
+            });
+        }
+    }
+
+    impl<K: Eq + Hash, S: BuildHasher> Clearable for HashSet<K, S> {
+        fn clear(&mut self) {
+            HashSet::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            HashSet::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| x.capacity() * core::mem::size_of::<K>());
+        }
+    }
+
+    impl<K, V, S> IsCleared for HashMap<K, V, S> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<K, S> IsCleared for HashSet<K, S> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+}
+
+#[cfg(feature = "indexmap")]
+mod indexmap_impls {
+    use super::*;
+    use indexmap::{IndexMap, IndexSet};
+
+    impl<K, V, S> Clearable for IndexMap<K, V, S> {
+        fn clear(&mut self) {
+            IndexMap::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            IndexMap::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| {
+                x.capacity() * core::mem::size_of::<(K, V)>()
+            });
+        }
+    }
+
+    impl<K, S> Clearable for IndexSet<K, S> {
+        fn clear(&mut self) {
+            IndexSet::clear(self);
+        }
+
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            IndexSet::clear(self);
+            self.shrink_to(limit);
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| x.capacity() * core::mem::size_of::<K>());
+        }
+    }
+
+    impl<K, V, S> IsCleared for IndexMap<K, V, S> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<K, S> IsCleared for IndexSet<K, S> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+}
+
+#[cfg(feature = "smallvec")]
+mod smallvec_impls {
+    use super::*;
+    use smallvec::{Array, SmallVec};
+
+    impl<A: Array> Clearable for SmallVec<A> {
+        fn clear(&mut self) {
+            SmallVec::clear(self);
+        }
+
+        /// `SmallVec` can only shrink to fit, which moves an empty vector back inline.
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            SmallVec::clear(self);
+            if self.capacity() > limit {
+                self.shrink_to_fit();
+            }
+        }
+
+        fn retained_capacity(&self) -> usize {
+            if self.spilled() {
+                self.capacity()
+            } else {
+                0
+            }
+        }
+
+        /// Only spilled capacity counts as retained, like `retained_capacity`.
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| {
+                x.retained_capacity() * core::mem::size_of::<A::Item>()
+            });
+        }
+    }
+
+    impl<A: Array> IsCleared for SmallVec<A> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+mod arrayvec_impls {
+    use super::*;
+    use arrayvec::{ArrayString, ArrayVec};
+
+    impl<T, const CAP: usize> Clearable for ArrayVec<T, CAP> {
+        fn clear(&mut self) {
+            ArrayVec::clear(self);
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |_| 0);
+        }
+    }
+
+    impl<const CAP: usize> Clearable for ArrayString<CAP> {
+        fn clear(&mut self) {
+            ArrayString::clear(self);
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |_| 0);
+        }
+    }
+
+    impl<T, const CAP: usize> IsCleared for ArrayVec<T, CAP> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+
+    impl<const CAP: usize> IsCleared for ArrayString<CAP> {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+mod bytes_impls {
+    use super::*;
+    use bytes::BytesMut;
+
+    impl Clearable for BytesMut {
+        fn clear(&mut self) {
+            BytesMut::clear(self);
+        }
+
+        /// `BytesMut` can't shrink in place, so an oversized buffer is replaced.
+        fn clear_and_shrink_to(&mut self, limit: usize) {
+            if self.capacity() > limit {
+                *self = BytesMut::with_capacity(limit);
+            } else {
+                BytesMut::clear(self);
+            }
+        }
+
+        fn retained_capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn clear_with_report(&mut self, report: &mut ClearReport) {
+            let dropped = self.len();
+            report.clear_and_record(self, dropped, |x| x.capacity());
+        }
+    }
+
+    impl IsCleared for BytesMut {
+        fn is_cleared(&self) -> bool {
+            self.is_empty()
         }
     }
 }
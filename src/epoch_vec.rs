@@ -0,0 +1,163 @@
+//! Fixed length vectors which clear in constant time.
+//!
+//! Every slot is stamped with the epoch it was last written in. Clearing bumps the current epoch,
+//! after which all older slots read as `T::default()`. The stamps are only rewritten when the
+//! epoch counter wraps around, so a smaller stamp type trades memory for more frequent resets.
+//!
+//! ```
+//! use kmacros::epoch_vec::EpochVec;
+//! use kmacros::Clearable;
+//!
+//! let mut visited = EpochVec::<bool>::new(1_000_000);
+//! visited[42] = true;
+//! assert!(visited[42]);
+//! visited.clear();
+//! assert!(!visited[42]);
+//! ```
+use crate::{Clearable, IsCleared};
+use alloc::vec::Vec;
+use core::ops::{Index, IndexMut};
+
+/// Unsigned integers usable as epoch stamps.
+pub trait Epoch: Copy + Eq + private::Sealed {
+    const ZERO: Self;
+    /// The next epoch, `None` when the counter wraps.
+    fn next(self) -> Option<Self>;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! epoch_impls {
+    ($($ty:ty)+) => {
+        $(
+            impl private::Sealed for $ty {}
+
+            impl Epoch for $ty {
+                const ZERO: Self = 0;
+
+                fn next(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )+
+    };
+}
+
+epoch_impls! { u8 u16 u32 u64 }
+
+#[derive(Debug, Clone)]
+pub struct EpochVec<T, E: Epoch = u32> {
+    values: Vec<T>,
+    stamps: Vec<E>,
+    /// Slots stamped with an older epoch read as `default`.
+    epoch: E,
+    default: T,
+}
+
+impl<T: Default, E: Epoch> EpochVec<T, E> {
+    /// `len` slots, all reading as `T::default()`.
+    pub fn new(len: usize) -> Self {
+        let mut vec = Self {
+            values: Vec::new(),
+            stamps: Vec::new(),
+            epoch: E::ZERO.next().expect("epochs have room for 1"),
+            default: T::default(),
+        };
+        vec.resize(len);
+        vec
+    }
+
+    /// Changes the number of slots. New slots read as `T::default()`.
+    pub fn resize(&mut self, len: usize) {
+        self.values.resize_with(len, T::default);
+        self.stamps.resize(len, E::ZERO);
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Whether slot `index` was written since the last clear.
+    pub fn is_written(&self, index: usize) -> bool {
+        self.stamps.get(index) == Some(&self.epoch)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let value = self.values.get(index)?;
+        Some(if self.stamps[index] == self.epoch {
+            value
+        } else {
+            &self.default
+        })
+    }
+
+    /// Resets a stale slot to `T::default()` before handing it out.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let value = self.values.get_mut(index)?;
+        let stamp = &mut self.stamps[index];
+        if *stamp != self.epoch {
+            *stamp = self.epoch;
+            *value = T::default();
+        }
+        Some(value)
+    }
+
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: T) {
+        self.values[index] = value;
+        self.stamps[index] = self.epoch;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.len()).map(move |i| &self[i])
+    }
+}
+
+impl<T: Default, E: Epoch> Index<usize> for EpochVec<T, E> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for length {}", index, self.len()),
+        }
+    }
+}
+
+impl<T: Default, E: Epoch> IndexMut<usize> for EpochVec<T, E> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        match self.get_mut(index) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for length {}", index, len),
+        }
+    }
+}
+
+/// Constant time, except when the epoch wraps and every stamp is reset.
+///
+/// The slots make up the fixed length rather than spare capacity, so `clear_and_shrink_to` keeps
+/// them and `retained_capacity` is 0, which also keeps `HighWatermark` from shrinking for nothing.
+impl<T, E: Epoch> Clearable for EpochVec<T, E> {
+    fn clear(&mut self) {
+        self.epoch = match self.epoch.next() {
+            Some(epoch) => epoch,
+            None => {
+                self.stamps.fill(E::ZERO);
+                E::ZERO.next().expect("epochs have room for 1")
+            }
+        };
+    }
+}
+
+impl<T, E: Epoch> IsCleared for EpochVec<T, E> {
+    fn is_cleared(&self) -> bool {
+        self.stamps.iter().all(|stamp| *stamp != self.epoch)
+    }
+}
@@ -1,11 +1,29 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod clearable;
 pub use clearable::*;
+#[cfg(feature = "alloc")]
+pub mod epoch_vec;
+#[cfg(feature = "std")]
+pub mod pool;
+#[cfg(feature = "alloc")]
+pub mod registry;
 
+#[cfg(feature = "kinds")]
+pub use enum_kinds::{self, EnumKind};
 pub use kmacros_shim::{self, *};
 #[cfg(feature = "proc")]
 pub use kproc_macros::*;
-#[cfg(feature = "kinds")]
-pub use enum_kinds::{self, EnumKind};
+
+/// Paths used by generated code, which can't rely on `std` being available.
+#[doc(hidden)]
+#[cfg(feature = "alloc")]
+pub mod __private {
+    pub use alloc::vec::Vec;
+}
 
 pub struct OptimizeCheckOutput<T, P> {
     pub function_name: &'static str,
@@ -17,8 +35,8 @@ pub struct OptimizeCheckOutput<T, P> {
 impl<T, P> OptimizeCheckOutput<T, P> {
     pub fn assert_equal(self) -> T
     where
-        T: PartialEq + std::fmt::Debug,
-        P: std::fmt::Debug,
+        T: PartialEq + core::fmt::Debug,
+        P: core::fmt::Debug,
     {
         let Self {
             function_name,
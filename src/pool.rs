@@ -0,0 +1,367 @@
+//! Free lists of [`Clearable`] objects, to reuse their allocations across requests or frames.
+//!
+//! Objects are handed out behind guards which clear them and hand them back to the pool on drop.
+//! When the pool is empty a new object is created with `Default::default()`. Once a pool holds
+//! `max_retained` objects, further returned ones are dropped.
+//!
+//! ```
+//! use kmacros::pool::Pool;
+//!
+//! let pool = Pool::<Vec<u8>>::with_max_retained(8);
+//! {
+//!     let mut buffer = pool.get();
+//!     buffer.extend_from_slice(b"hello");
+//! }
+//! let buffer = pool.get();
+//! assert!(buffer.is_empty());
+//! assert!(buffer.capacity() >= 5);
+//!
+//! let stats = pool.stats();
+//! assert_eq!((stats.hits, stats.misses, stats.size), (1, 1, 0));
+//! ```
+use crate::Clearable;
+use std::cell::{Cell, RefCell};
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::LocalKey;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Objects handed out from the pool.
+    pub hits: u64,
+    /// Objects which had to be created because the pool was empty.
+    pub misses: u64,
+    /// Objects currently held by the pool.
+    pub size: usize,
+}
+
+struct PoolInner<T> {
+    items: RefCell<Vec<T>>,
+    max_retained: Cell<usize>,
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+}
+
+/// A single threaded pool. Cloning it gives another handle to the same pool.
+pub struct Pool<T> {
+    inner: Rc<PoolInner<T>>,
+}
+
+impl<T> Clone for Pool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Clearable + Default> Default for Pool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clearable + Default> Pool<T> {
+    /// A pool without a limit on the number of retained objects.
+    pub fn new() -> Self {
+        Self::with_max_retained(usize::MAX)
+    }
+
+    pub fn with_max_retained(max_retained: usize) -> Self {
+        Self {
+            inner: Rc::new(PoolInner {
+                items: RefCell::new(Vec::new()),
+                max_retained: Cell::new(max_retained),
+                hits: Cell::new(0),
+                misses: Cell::new(0),
+            }),
+        }
+    }
+
+    pub fn get(&self) -> Pooled<T> {
+        let inner = &self.inner;
+        let value = inner.items.borrow_mut().pop();
+        let value = match value {
+            Some(value) => {
+                inner.hits.set(inner.hits.get() + 1);
+                value
+            }
+            None => {
+                inner.misses.set(inner.misses.get() + 1);
+                T::default()
+            }
+        };
+        Pooled {
+            value: Some(value),
+            pool: inner.clone(),
+        }
+    }
+
+    pub fn max_retained(&self) -> usize {
+        self.inner.max_retained.get()
+    }
+
+    /// Drops retained objects beyond the new limit.
+    pub fn set_max_retained(&self, max_retained: usize) {
+        self.inner.max_retained.set(max_retained);
+        let removed = {
+            let mut items = self.inner.items.borrow_mut();
+            let len = items.len();
+            items.split_off(len.min(max_retained))
+        };
+        drop(removed);
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            hits: self.inner.hits.get(),
+            misses: self.inner.misses.get(),
+            size: self.inner.items.borrow().len(),
+        }
+    }
+}
+
+/// An object from a [`Pool`], which is cleared and returned to it on drop.
+pub struct Pooled<T: Clearable> {
+    value: Option<T>,
+    pool: Rc<PoolInner<T>>,
+}
+
+impl<T: Clearable> Pooled<T> {
+    /// Takes the object out without returning it to the pool.
+    pub fn detach(mut self) -> T {
+        self.value
+            .take()
+            .expect("pooled value is present until drop")
+    }
+}
+
+impl<T: Clearable> Deref for Pooled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+            .as_ref()
+            .expect("pooled value is present until drop")
+    }
+}
+
+impl<T: Clearable> DerefMut for Pooled<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+            .as_mut()
+            .expect("pooled value is present until drop")
+    }
+}
+
+impl<T: Clearable> Drop for Pooled<T> {
+    fn drop(&mut self) {
+        let Some(mut value) = self.value.take() else {
+            return;
+        };
+        if self.pool.items.borrow().len() >= self.pool.max_retained.get() {
+            return;
+        }
+        // Not borrowing the pool while clearing, the value might hold objects from it.
+        value.clear();
+        value.debug_assert_cleared();
+        self.pool.items.borrow_mut().push(value);
+    }
+}
+
+struct SyncPoolInner<T> {
+    items: Mutex<Vec<T>>,
+    max_retained: AtomicUsize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// A pool which can be shared between threads. Cloning it gives another handle to the same pool.
+///
+/// ```
+/// use kmacros::pool::SyncPool;
+///
+/// let pool = SyncPool::<String>::new();
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| pool.get().push_str("hello"));
+///     }
+/// });
+/// assert!(pool.get().is_empty());
+/// ```
+pub struct SyncPool<T> {
+    inner: Arc<SyncPoolInner<T>>,
+}
+
+impl<T> Clone for SyncPool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Clearable + Default> Default for SyncPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clearable + Default> SyncPool<T> {
+    /// A pool without a limit on the number of retained objects.
+    pub fn new() -> Self {
+        Self::with_max_retained(usize::MAX)
+    }
+
+    pub fn with_max_retained(max_retained: usize) -> Self {
+        Self {
+            inner: Arc::new(SyncPoolInner {
+                items: Mutex::new(Vec::new()),
+                max_retained: AtomicUsize::new(max_retained),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    pub fn get(&self) -> SyncPooled<T> {
+        let inner = &self.inner;
+        let value = inner.items().pop();
+        let value = match value {
+            Some(value) => {
+                inner.hits.fetch_add(1, Ordering::Relaxed);
+                value
+            }
+            None => {
+                inner.misses.fetch_add(1, Ordering::Relaxed);
+                T::default()
+            }
+        };
+        SyncPooled {
+            value: Some(value),
+            pool: inner.clone(),
+        }
+    }
+
+    pub fn max_retained(&self) -> usize {
+        self.inner.max_retained.load(Ordering::Relaxed)
+    }
+
+    /// Drops retained objects beyond the new limit.
+    pub fn set_max_retained(&self, max_retained: usize) {
+        self.inner
+            .max_retained
+            .store(max_retained, Ordering::Relaxed);
+        let removed = {
+            let mut items = self.inner.items();
+            let len = items.len();
+            items.split_off(len.min(max_retained))
+        };
+        drop(removed);
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            size: self.inner.items().len(),
+        }
+    }
+}
+
+impl<T> SyncPoolInner<T> {
+    fn items(&self) -> std::sync::MutexGuard<'_, Vec<T>> {
+        self.items.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// An object from a [`SyncPool`], which is cleared and returned to it on drop.
+pub struct SyncPooled<T: Clearable> {
+    value: Option<T>,
+    pool: Arc<SyncPoolInner<T>>,
+}
+
+impl<T: Clearable> SyncPooled<T> {
+    /// Takes the object out without returning it to the pool.
+    pub fn detach(mut self) -> T {
+        self.value
+            .take()
+            .expect("pooled value is present until drop")
+    }
+}
+
+impl<T: Clearable> Deref for SyncPooled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+            .as_ref()
+            .expect("pooled value is present until drop")
+    }
+}
+
+impl<T: Clearable> DerefMut for SyncPooled<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+            .as_mut()
+            .expect("pooled value is present until drop")
+    }
+}
+
+impl<T: Clearable> Drop for SyncPooled<T> {
+    fn drop(&mut self) {
+        let Some(mut value) = self.value.take() else {
+            return;
+        };
+        let max_retained = self.pool.max_retained.load(Ordering::Relaxed);
+        if self.pool.items().len() >= max_retained {
+            return;
+        }
+        // Not holding the lock while clearing, the value might hold objects from this pool.
+        value.clear();
+        value.debug_assert_cleared();
+        let mut items = self.pool.items();
+        if items.len() < max_retained {
+            items.push(value);
+        }
+    }
+}
+
+/// A [`Pool`] per thread, backed by a `thread_local!`.
+///
+/// ```
+/// use kmacros::pool::{LocalPool, Pool};
+///
+/// thread_local! {
+///     static BUFFERS: Pool<Vec<u8>> = Pool::with_max_retained(4);
+/// }
+/// static POOL: LocalPool<Vec<u8>> = LocalPool::new(&BUFFERS);
+///
+/// POOL.get().push(1);
+/// assert!(POOL.get().is_empty());
+/// assert_eq!(POOL.stats().hits, 1);
+/// ```
+pub struct LocalPool<T: 'static> {
+    key: &'static LocalKey<Pool<T>>,
+}
+
+impl<T: Clearable + Default + 'static> LocalPool<T> {
+    pub const fn new(key: &'static LocalKey<Pool<T>>) -> Self {
+        Self { key }
+    }
+
+    pub fn get(&self) -> Pooled<T> {
+        self.key.with(Pool::get)
+    }
+
+    /// A handle to this thread's pool.
+    pub fn pool(&self) -> Pool<T> {
+        self.key.with(Pool::clone)
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        self.key.with(Pool::stats)
+    }
+}
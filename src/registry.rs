@@ -0,0 +1,204 @@
+//! A list of differently typed [`Clearable`] values which are reset together, e.g. at the end of
+//! a frame.
+//!
+//! Entries are cleared in registration order and can be disabled by name. The registry is itself
+//! `Clearable`, so `clear`, `clear_and_shrink_to`, `clear_group` and `clear_with_report` all go to
+//! every enabled entry. `clear_field` clears the enabled entries with the given name, or forwards
+//! the rest of a dotted path such as `"world.particles"` to them.
+//!
+//! ```
+//! use kmacros::registry::ClearRegistry;
+//! use kmacros::Clearable;
+//! use std::collections::HashMap;
+//!
+//! let mut events = vec!["spawn"];
+//! let mut positions = HashMap::from([(1, (0.0, 0.0))]);
+//! let mut registry = ClearRegistry::new();
+//! registry.register("events", &mut events);
+//! registry.register("positions", &mut positions);
+//! registry.register_boxed("scratch", Box::new(String::from("tmp")));
+//! registry.set_enabled("positions", false);
+//! registry.clear();
+//! drop(registry);
+//!
+//! assert!(events.is_empty());
+//! assert_eq!(positions.len(), 1);
+//! ```
+use crate::{ClearReport, Clearable};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+enum Target<'a> {
+    Borrowed(&'a mut (dyn Clearable + 'a)),
+    Owned(Box<dyn Clearable + 'a>),
+}
+
+struct Entry<'a> {
+    name: Cow<'static, str>,
+    target: Target<'a>,
+    enabled: bool,
+}
+
+impl<'a> Entry<'a> {
+    fn value(&mut self) -> &mut (dyn Clearable + 'a) {
+        match &mut self.target {
+            Target::Borrowed(value) => &mut **value,
+            Target::Owned(value) => &mut **value,
+        }
+    }
+
+    fn value_ref(&self) -> &(dyn Clearable + 'a) {
+        match &self.target {
+            Target::Borrowed(value) => &**value,
+            Target::Owned(value) => &**value,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ClearRegistry<'a> {
+    entries: Vec<Entry<'a>>,
+}
+
+impl<'a> ClearRegistry<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a borrowed value, enabled.
+    pub fn register(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: &'a mut (dyn Clearable + 'a),
+    ) -> &mut Self {
+        self.push(name.into(), Target::Borrowed(value))
+    }
+
+    /// Adds a value owned by the registry, enabled.
+    pub fn register_boxed(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: Box<dyn Clearable + 'a>,
+    ) -> &mut Self {
+        self.push(name.into(), Target::Owned(value))
+    }
+
+    fn push(&mut self, name: Cow<'static, str>, target: Target<'a>) -> &mut Self {
+        self.entries.push(Entry {
+            name,
+            target,
+            enabled: true,
+        });
+        self
+    }
+
+    /// Enables or disables every entry called `name`. Returns whether there was one.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let mut found = false;
+        for entry in self.entries.iter_mut().filter(|e| e.name == name) {
+            entry.enabled = enabled;
+            found = true;
+        }
+        found
+    }
+
+    /// Whether an entry called `name` is enabled, `None` if there is none.
+    pub fn is_enabled(&self, name: &str) -> Option<bool> {
+        self.entries
+            .iter()
+            .find(|e| e.name == name)
+            .map(|e| e.enabled)
+    }
+
+    /// Names of the entries, in registration order.
+    pub fn names(&self) -> impl Iterator<Item = &str> + use<'_, 'a> {
+        self.entries.iter().map(|e| &*e.name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn enabled(&mut self) -> impl Iterator<Item = &mut Entry<'a>> {
+        self.entries.iter_mut().filter(|e| e.enabled)
+    }
+}
+
+/// Clears the enabled entries in registration order.
+///
+/// ```
+/// use kmacros::registry::ClearRegistry;
+/// use kmacros::{ClearReport, Clearable};
+///
+/// let mut ids = vec![1u32, 2];
+/// let mut registry = ClearRegistry::new();
+/// registry.register("ids", &mut ids);
+///
+/// let mut report = ClearReport::new();
+/// registry.clear_with_report(&mut report);
+/// assert_eq!(report.entries()[0].path, "ids");
+/// assert_eq!(report.dropped(), 2);
+/// ```
+impl Clearable for ClearRegistry<'_> {
+    fn clear(&mut self) {
+        for entry in self.enabled() {
+            entry.value().clear();
+        }
+    }
+
+    fn clear_and_shrink_to(&mut self, limit: usize) {
+        for entry in self.enabled() {
+            entry.value().clear_and_shrink_to(limit);
+        }
+    }
+
+    fn retained_capacity(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.enabled)
+            .map(|e| e.value_ref().retained_capacity())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn clear_group(&mut self, group: &str) {
+        for entry in self.enabled() {
+            entry.value().clear_group(group);
+        }
+    }
+
+    fn clear_field(&mut self, name: &str) -> bool {
+        let mut found = false;
+        for entry in self.enabled() {
+            if entry.name == name {
+                entry.value().clear();
+                found = true;
+            } else if let Some(path) = name
+                .strip_prefix(&*entry.name)
+                .and_then(|rest| rest.strip_prefix('.'))
+            {
+                found |= entry.value().clear_field(path);
+            }
+        }
+        found
+    }
+
+    fn clear_with_report(&mut self, report: &mut ClearReport) {
+        for entry in self.enabled() {
+            report.enter(&entry.name);
+            entry.value().clear_with_report(report);
+            report.leave();
+        }
+    }
+
+    fn debug_assert_cleared(&self) {
+        for entry in self.entries.iter().filter(|e| e.enabled) {
+            entry.value_ref().debug_assert_cleared();
+        }
+    }
+}